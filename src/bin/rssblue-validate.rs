@@ -0,0 +1,206 @@
+//! Validates a podcast feed from the command line.
+//!
+//! Runs the same analysis as the Validator page and exits with a non-zero status if any errors
+//! were found, so that it can be used to gate feed deployment.
+
+use app::validator::redirects::{self, Hop};
+use app::validator::syntax::{self, Diagnostic};
+use app::validator::{self, Error, Node, Severity};
+use std::io::Read;
+use std::process::ExitCode;
+
+/// Redirects to follow before giving up.
+const MAX_REDIRECTS: usize = 10;
+
+const USAGE: &str = "Usage: rssblue-validate [FILE | URL]
+
+Validates the podcast feed in FILE. If FILE is omitted or is \"-\", the feed is read from the
+standard input. If an http or https URL is given instead, the feed is fetched from it, and the
+redirects on the way are checked too.
+
+Exit status is 0 if no errors were found (warnings and info messages are allowed), 1 if the feed
+has errors, and 2 if the feed could not be read or parsed.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let path = match args.as_slice() {
+        [] => None,
+        [arg] if arg == "-h" || arg == "--help" => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        [arg] if arg == "-" => None,
+        [arg] => Some(arg.as_str()),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let fetched = match path {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => fetch_feed(url),
+        _ => read_feed(path)
+            .map(|bytes| (bytes, Vec::new()))
+            .map_err(|e| e.to_string()),
+    };
+    let (bytes, hops) = match fetched {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("Could not read the feed ({e})");
+            return ExitCode::from(2);
+        }
+    };
+
+    let (xml, diagnostics) = syntax::analyze(&bytes);
+    print_diagnostics(&xml, &diagnostics);
+
+    let result = match hops.last() {
+        Some(hop) => validator::validate_fetched(&xml, &hop.url),
+        None => validator::validate_str(&xml),
+    };
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Could not parse the feed ({e})");
+            return ExitCode::from(2);
+        }
+    };
+
+    if !report.has_podcast_tags() {
+        println!("No podcast namespace tags found.");
+    }
+
+    print_node(&report.root, 0);
+    print_redirects(&hops, &redirects::analyze(&hops, &xml));
+
+    let count = |severity| {
+        report.count(severity)
+            + diagnostics
+                .iter()
+                .filter(|d| d.error.severity() == severity)
+                .count()
+    };
+    if report.has_errors() || count(Severity::Error) > 0 {
+        println!(
+            "Found {} errors, {} warnings and {} info messages.",
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info),
+        );
+        ExitCode::FAILURE
+    } else {
+        println!("Our analysis has not found any errors in the feed.");
+        ExitCode::SUCCESS
+    }
+}
+
+/// Reads the undecoded feed, so that its encoding can be checked.
+fn read_feed(path: Option<&str>) -> std::io::Result<Vec<u8>> {
+    match path {
+        Some(path) => std::fs::read(path),
+        None => {
+            let mut bytes = Vec::new();
+            std::io::stdin().read_to_end(&mut bytes)?;
+            Ok(bytes)
+        }
+    }
+}
+
+/// Fetches the feed, following the redirects one by one to record them.
+fn fetch_feed(url: &str) -> Result<(Vec<u8>, Vec<Hop>), String> {
+    let client = reqwest_wasm::blocking::Client::builder()
+        .redirect(reqwest_wasm::redirect::Policy::none())
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut url = url::Url::parse(url).map_err(|e| e.to_string())?;
+    let mut hops = Vec::new();
+
+    loop {
+        let resp = client.get(url.clone()).send().map_err(|e| e.to_string())?;
+        let status = resp.status();
+        hops.push(Hop {
+            url: url.to_string(),
+            status: Some(status.as_u16()),
+        });
+
+        if !status.is_redirection() {
+            if !status.is_success() {
+                return Err(format!("{url} responded with {status}"));
+            }
+            let bytes = resp.bytes().map_err(|e| e.to_string())?;
+            return Ok((bytes.to_vec(), hops));
+        }
+        if hops.len() > MAX_REDIRECTS {
+            return Err(format!("more than {MAX_REDIRECTS} redirects"));
+        }
+        let location = resp
+            .headers()
+            .get("location")
+            .and_then(|x| x.to_str().ok())
+            .ok_or_else(|| format!("{url} responded with {status} without a location"))?;
+        url = url.join(location).map_err(|e| e.to_string())?;
+    }
+}
+
+fn symbol(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "✗",
+        Severity::Warning => "!",
+        Severity::Info => "i",
+    }
+}
+
+/// Prints the problems with the encoding and the syntax, with the lines they are on.
+fn print_diagnostics(xml: &str, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        let (excerpt, column) = syntax::excerpt(xml, &diagnostic.span);
+        println!(
+            "{} {} (line {}, col {})",
+            symbol(diagnostic.error.severity()),
+            diagnostic.error,
+            diagnostic.span.line,
+            diagnostic.span.column
+        );
+        println!("    {excerpt}");
+        println!("    {}^", " ".repeat(column));
+    }
+}
+
+fn print_redirects(hops: &[Hop], errors: &[Error]) {
+    if hops.len() > 1 {
+        println!("Redirects:");
+        for hop in hops {
+            match hop.status {
+                Some(status) => println!("  {status} {}", hop.url),
+                None => println!("  {}", hop.url),
+            }
+        }
+    }
+    for error in errors {
+        println!("{} {error}", symbol(error.severity()));
+    }
+}
+
+/// Prints the branches of the tree that lead to findings.
+fn print_node(node: &Node, depth: usize) {
+    if node.max_severity().is_none() {
+        return;
+    }
+
+    let indent = "  ".repeat(depth);
+    println!("{indent}<{}>", node.name);
+    for error in &node.errors {
+        let symbol = symbol(error.severity());
+        match node.error_span(error) {
+            Some(span) => println!(
+                "{indent}  {symbol} {error} (line {}, col {})",
+                span.line, span.column
+            ),
+            None => println!("{indent}  {symbol} {error}"),
+        }
+    }
+    for child in &node.children {
+        print_node(child, depth + 1);
+    }
+}
@@ -12,6 +12,9 @@ pub use plot_op3::PlotOp3;
 mod validator;
 pub use validator::Validator;
 
+mod validator_rules;
+pub use validator_rules::{ValidatorRule, ValidatorRules};
+
 pub mod utils;
 
 #[derive(Prop)]
@@ -1,7 +1,7 @@
 use crate::components::utils;
+use app::validator::podcast_guid;
 use sycamore::prelude::*;
 use url::Url;
-use uuid::Uuid;
 
 #[component]
 pub fn PodcastGuid<G: Html>(cx: Scope) -> View<G> {
@@ -87,11 +87,6 @@ pub fn PodcastGuid<G: Html>(cx: Scope) -> View<G> {
 }
 
 fn update_guid(url_str: String) -> (Option<String>, Vec<String>) {
-    const NAMESPACE_PODCAST: uuid::Uuid = uuid::Uuid::from_bytes([
-        0xea, 0xd4, 0xc2, 0x36, 0xbf, 0x58, 0x58, 0xc6, 0xa2, 0xc6, 0xa6, 0xb2, 0x8d, 0x12, 0x8c,
-        0xb6,
-    ]);
-
     let mut warnings = vec![];
 
     if url_str.is_empty() {
@@ -124,7 +119,7 @@ fn update_guid(url_str: String) -> (Option<String>, Vec<String>) {
         }
     }
 
-    let uuid = Uuid::new_v5(&NAMESPACE_PODCAST, url_str.as_bytes()).to_string();
+    let uuid = podcast_guid::hash(&url_str);
 
     (Some(uuid), warnings)
 }
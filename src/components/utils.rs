@@ -1,5 +1,5 @@
 use sycamore::prelude::*;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 
 pub enum AlertType {
     Success,
@@ -153,3 +153,38 @@ pub fn remove_from_storage(key: &str) -> Result<(), String> {
         .remove_item(key)
         .map_err(|err| err.as_string().unwrap_or("Unknown error".to_string()))
 }
+
+/// Lets the user save `contents` as a file.
+pub fn download_file(filename: &str, mime_type: &str, contents: &str) -> Result<(), String> {
+    let to_string = |err: JsValue| err.as_string().unwrap_or("Unknown error".to_string());
+
+    let window = web_sys::window().ok_or("No window".to_string())?;
+    let document = window.document().ok_or("No document".to_string())?;
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime_type);
+    let blob =
+        web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).map_err(to_string)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(to_string)?;
+
+    let link: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(to_string)?
+        .dyn_into()
+        .map_err(|_| "Could not create a link".to_string())?;
+    link.set_href(&url);
+    link.set_download(filename);
+    link.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(to_string)
+}
+
+/// Reads the raw bytes of a file selected or dropped by the user, so that its encoding can still
+/// be checked.
+pub async fn read_file(file: web_sys::File) -> Result<Vec<u8>, String> {
+    let buffer = wasm_bindgen_futures::JsFuture::from(file.array_buffer())
+        .await
+        .map_err(|err| err.as_string().unwrap_or("Unknown error".to_string()))?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
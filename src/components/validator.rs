@@ -1,23 +1,78 @@
 use crate::components::utils;
+use app::validator::adoption::{self, Adoption};
+use app::validator::diff::{Change, NodeDiff};
+use app::validator::fix;
+use app::validator::redirects::{self, Hop};
+use app::validator::rules::{rule_id, RuleLevel, RuleSettings};
+use app::validator::source::Span;
+use app::validator::summary::{self, Summary};
+use app::validator::syntax::{self, Diagnostic};
+use app::validator::transport::{self, Response};
+use app::validator::{self, Error, Node, Report, Severity, Value, NODE_VALUE};
 use sycamore::prelude::*;
 use sycamore::suspense::{use_transition, Suspense};
 use url::Url;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
 
-#[derive(Debug, Clone)]
-struct ProgramError<G: Html> {
-    description: View<G>,
-    error: Option<(String, String)>,
+const CORS_PROXY_URL: &str = "https://proxy.rssblue.com?url=";
+
+/// Where the feed to validate comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum InputMode {
+    Url,
+    Source,
+    Diff,
 }
 
+/// One of the versions of a feed to compare.
 #[derive(Debug, Clone, PartialEq)]
-enum Value {
-    Text(String),
-    Object(String),
+enum FeedInput {
     Url(String),
+    Source(String),
+}
+
+/// File uploaded into the source box.
+#[derive(Debug, Clone, PartialEq)]
+struct SourceFile {
+    /// Raw bytes of the file.
+    bytes: Vec<u8>,
+    /// Text shown in the source box.
+    text: String,
+}
+
+impl SourceFile {
+    fn new(bytes: Vec<u8>) -> Self {
+        let (text, _) = syntax::analyze(&bytes);
+        SourceFile { bytes, text }
+    }
+}
+
+/// HTTP response of a fetched feed and the redirects that led to it.
+#[derive(Debug, Clone)]
+struct Transport {
+    response: Response,
+    hops: Vec<Hop>,
+    /// Problems with the response and the redirects.
+    errors: Vec<Error>,
+}
+
+/// Bytes to validate for the content of the source box. These are the raw bytes of the uploaded
+/// file unless the text has been edited since, so that its encoding and byte order mark are
+/// checked. Pasted text has already been decoded by the browser and is validated as UTF-8.
+fn source_bytes(text: &str, file: Option<&SourceFile>) -> Vec<u8> {
+    match file {
+        Some(file) if file.text == text => file.bytes.clone(),
+        _ => text.as_bytes().to_vec(),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ProgramError<G: Html> {
+    description: View<G>,
+    error: Option<(String, String)>,
 }
 
-fn md_to_html(md: &str) -> String {
+pub(super) fn md_to_html(md: &str) -> String {
     let mut options = comrak::ComrakOptions::default();
     options.parse.smart = true;
 
@@ -55,6 +110,18 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
     let url_str = create_signal(cx, String::new());
     let transition = use_transition(cx);
     let show_results = create_signal(cx, false);
+    let input_mode = create_signal(cx, InputMode::Url);
+    // Validate XML pasted or uploaded by the user instead of fetching it.
+    let source_str = create_signal(cx, String::new());
+    let source_file = create_signal(cx, None::<SourceFile>);
+    let submitted_source = create_signal(cx, None::<Vec<u8>>);
+    let file_error = create_signal(cx, None::<String>);
+    // Compare an older version of the feed with a newer one.
+    let old_url_str = create_signal(cx, String::new());
+    let new_url_str = create_signal(cx, String::new());
+    let new_source_str = create_signal(cx, String::new());
+    let paste_new_version = create_signal(cx, false);
+    let submitted_diff = create_signal(cx, None::<(String, FeedInput)>);
     // Use CORS proxy to avoid CORS issues.
     let use_proxy = create_signal(cx, false);
 
@@ -93,6 +160,41 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
 
     let fetch_feed = move |x| transition.start(move || fetching_data.set(x), || ());
 
+    let load_file = move |file: web_sys::File| {
+        sycamore::futures::spawn_local_scoped(cx, async move {
+            match utils::read_file(file).await {
+                Ok(bytes) => {
+                    let file = SourceFile::new(bytes);
+                    source_str.set(file.text.clone());
+                    source_file.set(Some(file));
+                    file_error.set(None);
+                }
+                Err(e) => file_error.set(Some(e)),
+            }
+        });
+    };
+    let on_file_change = move |e: web_sys::Event| {
+        let file = e
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0));
+        if let Some(file) = file {
+            load_file(file);
+        }
+    };
+    let on_drop = move |e: web_sys::Event| {
+        e.prevent_default();
+        let file = e
+            .dyn_ref::<web_sys::DragEvent>()
+            .and_then(|e| e.data_transfer())
+            .and_then(|data| data.files())
+            .and_then(|files| files.get(0));
+        if let Some(file) = file {
+            load_file(file);
+        }
+    };
+
     create_effect(cx, move || {
         if *fetching_data.get() {
             input_cls.set("bg-gray-100".to_string());
@@ -127,7 +229,7 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
         }
 
         p(class="mb-7") {
-            "This validator only checks the " em { "podcast namespace" } " elements and only analyzes the " em { "feed" } " itself. For other namespaces and media checks, you can try "
+            "This validator checks the core " em { "RSS" } ", " em { "Apple Podcasts" } " and " em { "podcast namespace" } " elements and only analyzes the " em { "feed" } " itself. For other namespaces and media checks, you can try "
         utils::Link(url="https://validator.livewire.io/".to_string(), text="Livewire Podcast Validator".to_string(), new_tab=true)
         ", "
         utils::Link(url="https://www.castfeedvalidator.com/".to_string(), text="Cast Feed Validator".to_string(), new_tab=true)
@@ -144,6 +246,144 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
                 style="display: none",
                 aria-hidden="true"
             ){}
+        div(class="flex flex-row flex-wrap gap-2 text-sm") {
+            button(
+                class=tab_cls(*input_mode.get() == InputMode::Url),
+                type="button",
+                on:click=move |_| input_mode.set(InputMode::Url),
+            ) { "Fetch from URL" }
+            button(
+                class=tab_cls(*input_mode.get() == InputMode::Source),
+                type="button",
+                on:click=move |_| input_mode.set(InputMode::Source),
+            ) { "Paste or upload XML" }
+            button(
+                class=tab_cls(*input_mode.get() == InputMode::Diff),
+                type="button",
+                on:click=move |_| input_mode.set(InputMode::Diff),
+            ) { "Compare two versions" }
+        }
+
+        (if *input_mode.get() == InputMode::Diff {
+            view! { cx,
+                div {
+                    label(for="old-url") { "Old version's URL" }
+                    input(
+                        class="input-text",
+                        spellcheck=false,
+                        type="url",
+                        id="old-url",
+                        placeholder="https://example.com/feed.xml",
+                        autocomplete="off",
+                        bind:value=old_url_str,
+                    )
+                }
+                (if *paste_new_version.get() {
+                    view! { cx,
+                        div {
+                            label(for="new-source") { "New version's XML" }
+                            textarea(
+                                class="input-text font-mono text-xs h-40",
+                                spellcheck=false,
+                                id="new-source",
+                                bind:value=new_source_str,
+                            )
+                        }
+                    }
+                } else {
+                    view! { cx,
+                        div {
+                            label(for="new-url") { "New version's URL" }
+                            input(
+                                class="input-text",
+                                spellcheck=false,
+                                type="url",
+                                id="new-url",
+                                placeholder="https://staging.example.com/feed.xml",
+                                autocomplete="off",
+                                bind:value=new_url_str,
+                            )
+                        }
+                    }
+                })
+                div(class="flex flex-row flex-wrap items-center gap-3") {
+                    div(class="cursor-pointer") {
+                        input(
+                            id="paste-new-version",
+                            type="checkbox",
+                            class="input-checkbox",
+                            bind:checked=paste_new_version,
+                        )
+                        label(class="ml-3 cursor-pointer", for="paste-new-version") {
+                            "Paste the new version"
+                        }
+                    }
+                    div(class="cursor-pointer") {
+                        input(
+                            id="use-proxy-diff",
+                            type="checkbox",
+                            class="input-checkbox",
+                            bind:checked=use_proxy,
+                        )
+                        label(class="ml-3 cursor-pointer", for="use-proxy-diff") {
+                            "Route requests through RSS Blue"
+                        }
+                    }
+                    button(
+                        class="btn-base btn-primary rounded-xl ml-auto",
+                        type="button",
+                        on:click=move |_| {
+                            let new = if *paste_new_version.get() {
+                                FeedInput::Source(new_source_str.get().to_string())
+                            } else {
+                                FeedInput::Url(new_url_str.get().trim().to_string())
+                            };
+                            submitted_diff.set(Some((old_url_str.get().trim().to_string(), new)));
+                        },
+                    ) { "Compare" }
+                }
+            }
+        } else if *input_mode.get() == InputMode::Source {
+            view! { cx,
+                div {
+                    label(for="source") { "Feed's XML" }
+                    textarea(
+                        class="input-text font-mono text-xs h-64",
+                        spellcheck=false,
+                        id="source",
+                        placeholder="Paste the feed here or drop a file on this box.",
+                        bind:value=source_str,
+                        on:dragover=|e: web_sys::Event| e.prevent_default(),
+                        on:drop=on_drop,
+                    )
+                }
+                div(class="flex flex-row flex-wrap items-center gap-3") {
+                    input(
+                        type="file",
+                        accept=".xml,.rss,application/xml,application/rss+xml,text/xml",
+                        class="text-sm file:btn-base file:btn-primary file:rounded-xl file:border-0 file:py-1",
+                        on:change=on_file_change,
+                    )
+                    button(
+                        class="btn-base btn-primary rounded-xl ml-auto",
+                        type="button",
+                        disabled=source_str.get().trim().is_empty(),
+                        on:click=move |_| submitted_source.set(Some(source_bytes(
+                            &source_str.get(),
+                            source_file.get().as_ref().as_ref(),
+                        ))),
+                    ) { "Test feed" }
+                }
+                (if let Some(e) = file_error.get().as_ref() {
+                    view! { cx,
+                        utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not read the file ({e})"))
+                    }
+                } else {
+                    view! { cx, }
+                })
+            }
+        } else {
+            view! { cx,
         div{
             label(for="url") { "Feed's URL" }
         div(class="grid grid-cols-4") {
@@ -189,9 +429,31 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
         }
     }
     }
+            }
+        })
     }
 
-        (if *show_results.get() {
+        (if *input_mode.get() == InputMode::Diff {
+            match submitted_diff.get().as_ref() {
+                Some((old_url, new)) => {
+                    let (old_url, new) = (old_url.clone(), new.clone());
+                    view! { cx,
+                        Suspense(fallback=view! { cx, "Loading..." }) {
+                            CompareFeeds(old_url=old_url, new=new, use_proxy=*use_proxy.get_untracked())
+                        }
+                    }
+                }
+                None => view! { cx, },
+            }
+        } else if *input_mode.get() == InputMode::Source {
+            match submitted_source.get().as_ref() {
+                Some(source) => {
+                    let (source, diagnostics) = syntax::analyze(source);
+                    view! { cx, ValidateSource(source=source, feed_url=None, diagnostics=diagnostics, transport=None) }
+                }
+                None => view! { cx, },
+            }
+        } else if *show_results.get() {
             view!{cx,
                 Suspense(fallback=view! { cx, }) {
                     Validate(url=url_str.get().to_string(), use_proxy=*use_proxy.get())
@@ -214,8 +476,6 @@ pub fn Validator<G: Html>(cx: Scope) -> View<G> {
 
 #[component(inline_props)]
 pub async fn Validate<'a, G: Html>(cx: Scope<'a>, url: String, use_proxy: bool) -> View<G> {
-    const CORS_PROXY_URL: &str = "https://proxy.rssblue.com?url=";
-
     // Set 'url' query parameter.
     if let Some(window) = web_sys::window() {
         if let Ok(href) = window.location().href() {
@@ -248,12 +508,14 @@ pub async fn Validate<'a, G: Html>(cx: Scope<'a>, url: String, use_proxy: bool)
         };
     }
 
+    let feed_url = url.to_string();
     let url = if use_proxy {
         format!("{}{}", CORS_PROXY_URL, url)
     } else {
         url.to_string()
     };
 
+    let start = js_sys::Date::now();
     let resp = reqwest_wasm::get(url).await;
 
     let resp = match resp {
@@ -287,8 +549,38 @@ pub async fn Validate<'a, G: Html>(cx: Scope<'a>, url: String, use_proxy: bool)
     };
 
     let status = resp.status();
-
-    let text = match resp.text().await {
+    // Browsers follow redirects without revealing them, except for the final URL. The proxy
+    // follows them itself and does not reveal even that, so [`DisplayTransport`] says they are
+    // hidden.
+    let final_url = resp.url().to_string();
+    let hops = if use_proxy || final_url == feed_url {
+        vec![Hop {
+            url: feed_url.clone(),
+            status: Some(status.as_u16()),
+        }]
+    } else {
+        vec![
+            Hop {
+                url: feed_url.clone(),
+                status: None,
+            },
+            Hop {
+                url: final_url,
+                status: Some(status.as_u16()),
+            },
+        ]
+    };
+    let headers = resp
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+            (name.as_str().to_string(), value)
+        })
+        .collect();
+
+    // The raw bytes, because the text would already be decoded as UTF-8.
+    let bytes = match resp.bytes().await {
         Ok(x) => x,
         Err(e) => {
             return view! {cx,
@@ -300,70 +592,553 @@ pub async fn Validate<'a, G: Html>(cx: Scope<'a>, url: String, use_proxy: bool)
     if !status.is_success() {
         let program_error = ProgramError {
             description: view! { cx, (format!("Could not fetch the feed ({})", status)) },
-            error: Some(("Response".to_string(), text)),
+            error: Some((
+                "Response".to_string(),
+                String::from_utf8_lossy(&bytes).to_string(),
+            )),
         };
         return view! { cx, DisplayProgramError(program_error=program_error) };
     }
+    let (text, diagnostics) = syntax::analyze(&bytes);
+
+    let response = Response {
+        status: status.as_u16(),
+        headers,
+        body_size: bytes.len(),
+        elapsed_ms: Some(js_sys::Date::now() - start),
+        via_proxy: use_proxy,
+    };
+
+    let mut errors = transport::analyze(&response);
+    errors.extend(redirects::analyze(&hops, &text));
+    let transport = Transport {
+        response,
+        hops,
+        errors,
+    };
+
+    view! { cx,
+        ValidateSource(source=text, feed_url=Some(feed_url), diagnostics=diagnostics, transport=Some(transport))
+    }
+}
+
+/// Shows the redirects and the headers of the response that matter for feeds, and problems with
+/// them.
+#[component(inline_props)]
+fn DisplayTransport<'a, G: Html>(
+    cx: Scope<'a>,
+    transport: Transport,
+    settings: &'a ReadSignal<RuleSettings>,
+) -> View<G> {
+    let Transport {
+        response,
+        hops,
+        errors,
+    } = transport;
+    let errors = create_memo(cx, move || {
+        errors
+            .iter()
+            .filter_map(|error| {
+                settings
+                    .get()
+                    .apply_to_error(&Node::default(), error.clone())
+            })
+            .collect::<Vec<_>>()
+    });
+    let max_severity = create_memo(cx, || {
+        errors.get().iter().map(|error| error.severity()).max()
+    });
+    let summary_cls = move || {
+        format!(
+            "font-bold {}",
+            max_severity.get().map_or("", severity_text_cls)
+        )
+    };
+    let via_proxy = response.via_proxy;
+
+    let header = |name: &str| match response.header(name) {
+        Some(value) => value.to_string(),
+        None if response.is_exposed(name) => "none".to_string(),
+        None => "not exposed to the browser".to_string(),
+    };
+    let compression = match (
+        response.header("content-encoding"),
+        response.is_compressed(),
+    ) {
+        (Some(encoding), _) => encoding.to_string(),
+        (None, Some(true)) => "compressed".to_string(),
+        (None, Some(false)) => "none".to_string(),
+        (None, None) => "not exposed to the browser".to_string(),
+    };
+    let rows = vec![
+        ("Status", response.status.to_string()),
+        ("Content-Type", header("content-type")),
+        (
+            "Charset",
+            response.charset().unwrap_or("not specified").to_string(),
+        ),
+        ("Content-Encoding", compression),
+        ("Content-Length", header("content-length")),
+        ("ETag", header("etag")),
+        ("Last-Modified", header("last-modified")),
+        ("Cache-Control", header("cache-control")),
+        (
+            "Access-Control-Allow-Origin",
+            header("access-control-allow-origin"),
+        ),
+        ("Body size", transport::format_size(response.body_size)),
+        (
+            "Time",
+            match response.elapsed_ms {
+                Some(ms) => format!("{:.0} ms", ms),
+                None => "unknown".to_string(),
+            },
+        ),
+    ];
+    let rows = View::new_fragment(
+        rows.into_iter()
+            .map(|(name, value)| {
+                view! { cx,
+                    tr {
+                        td(class="pr-3") { code { (name) } }
+                        td(class="break-all") { (value) }
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let errors = view! { cx,
+        Indexed(
+            iterable=errors,
+            view=|cx, error| {
+                let marker_cls = severity_marker_cls(error.severity());
+                let id = rule_id(&Node::default(), &error);
+                view! { cx,
+                    li(class=format!("my-0 {marker_cls}")) {
+                        DisplayError(error=error, rule_id=Some(id))
+                    }
+                }
+            },
+        )
+    };
+
+    let hops = if hops.len() > 1 {
+        let hops = View::new_fragment(
+            hops.into_iter()
+                .map(|hop| {
+                    let status = match hop.status {
+                        Some(status) => status.to_string(),
+                        None => "redirect".to_string(),
+                    };
+                    view! { cx,
+                        li(class="my-0") {
+                            code(class="mr-2") { (status) }
+                            span(class="break-all") { (hop.url) }
+                        }
+                    }
+                })
+                .collect(),
+        );
+        view! { cx,
+            p(class="mb-0") { "Redirects:" }
+            ol(class="mt-0") { (hops) }
+            p(class="text-gray-500") { "Only the first and the last URL are shown. Browsers hide the redirects in between and the statuses of all redirects, so chains of redirects and temporary redirects cannot be detected here. The command-line validator shows every hop." }
+        }
+    } else if via_proxy {
+        view! { cx,
+            p(class="text-gray-500") { "Redirects are followed by the proxy, which does not reveal them, so they are not shown or checked. Stop routing requests through RSS Blue to see where the feed was redirected." }
+        }
+    } else {
+        view! { cx, }
+    };
+
+    view! { cx,
+        details(class="mb-5 text-sm", open=max_severity.get().is_some()) {
+            summary(class=summary_cls()) { "Transport" }
+            (if via_proxy {
+                view! { cx,
+                    p(class="text-gray-500") { "The feed was fetched through the RSS Blue proxy, so some headers may differ from those of the feed host." }
+                }
+            } else {
+                view! { cx, }
+            })
+            ul(class="my-2") {
+                (errors)
+            }
+            (hops)
+            table(class="w-full") {
+                tbody {
+                    (rows)
+                }
+            }
+        }
+    }
+}
+
+/// Analyzes the XML of a feed, checking it against `feed_url` if it was fetched.
+///
+/// `diagnostics` are the problems found in the source by [`syntax::analyze`], and `transport` is
+/// the response the feed was fetched with. The rule settings apply to the findings of all three.
+#[component(inline_props)]
+fn ValidateSource<'a, G: Html>(
+    cx: Scope<'a>,
+    source: String,
+    feed_url: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+    transport: Option<Transport>,
+) -> View<G> {
+    const VALIDATOR_STORAGE_KEY_RULE_SETTINGS: &str = "validator_rule_settings";
+
+    let storage_error = create_signal(cx, None::<String>);
+    let initial_settings = match utils::get_from_storage(VALIDATOR_STORAGE_KEY_RULE_SETTINGS) {
+        Ok(Some(s)) => serde_json::from_str(&s).unwrap_or_default(),
+        Ok(None) => RuleSettings::default(),
+        Err(e) => {
+            storage_error.set(Some(e));
+            RuleSettings::default()
+        }
+    };
+    let settings = create_signal(cx, initial_settings);
+    create_effect(cx, move || {
+        let result = if settings.get().0.is_empty() {
+            utils::remove_from_storage(VALIDATOR_STORAGE_KEY_RULE_SETTINGS)
+        } else {
+            serde_json::to_string(&*settings.get())
+                .map_err(|e| e.to_string())
+                .and_then(|s| utils::set_in_storage(VALIDATOR_STORAGE_KEY_RULE_SETTINGS, &s))
+        };
+        if let Err(e) = result {
+            storage_error.set(Some(e));
+        }
+    });
 
-    let feed = match badpod::from_str(&text) {
+    // Findings about the response and the source rather than about the elements of the feed.
+    let mut other_errors: Vec<Error> = diagnostics.iter().map(|d| d.error.clone()).collect();
+    let transport = match transport {
+        Some(transport) => {
+            other_errors.extend(transport.errors.iter().cloned());
+            view! { cx, DisplayTransport(transport=transport, settings=settings) }
+        }
+        None => view! { cx, },
+    };
+
+    let result = match &feed_url {
+        Some(feed_url) => validator::validate_fetched(&source, feed_url),
+        None => validator::validate_str(&source),
+    };
+    let syntax = if diagnostics.is_empty() {
+        view! { cx, }
+    } else {
+        let source = source.clone();
+        view! { cx, DisplaySyntax(source=source, diagnostics=diagnostics, settings=settings) }
+    };
+    let report = match result {
         Ok(x) => x,
         Err(e) => {
             return view! {cx,
+                (transport)
                 utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not parse the feed ({e})"))
+                (syntax)
             }
         }
     };
 
-    let root_node = analyze_rss(&feed);
+    view! { cx,
+        (transport)
+        (syntax)
+        DisplayReport(report=report, feed_url=feed_url, source=source, other_errors=other_errors, settings=settings, storage_error=storage_error)
+    }
+}
+
+/// Shows the problems with the encoding and the syntax of the source, with the lines they are on.
+#[component(inline_props)]
+fn DisplaySyntax<'a, G: Html>(
+    cx: Scope<'a>,
+    source: String,
+    diagnostics: Vec<Diagnostic>,
+    settings: &'a ReadSignal<RuleSettings>,
+) -> View<G> {
+    let diagnostics = create_memo(cx, move || {
+        diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let error = settings
+                    .get()
+                    .apply_to_error(&Node::default(), diagnostic.error.clone())?;
+                Some(Diagnostic {
+                    error,
+                    span: diagnostic.span,
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    let max_severity = create_memo(cx, || {
+        diagnostics.get().iter().map(|d| d.error.severity()).max()
+    });
+    let summary_cls = move || {
+        format!(
+            "font-bold {}",
+            max_severity.get().map_or("", severity_text_cls)
+        )
+    };
+    let source = create_ref(cx, source);
+
+    let items = view! { cx,
+        Indexed(
+            iterable=diagnostics,
+            view=move |cx, diagnostic| {
+                let marker_cls = severity_marker_cls(diagnostic.error.severity());
+                let id = rule_id(&Node::default(), &diagnostic.error);
+                let position = format!(
+                    "Line {}, column {}",
+                    diagnostic.span.line, diagnostic.span.column
+                );
+                let (excerpt, column) = syntax::excerpt(source, &diagnostic.span);
+                let caret = format!("{}^", " ".repeat(column));
+                view! { cx,
+                    li(class=format!("my-0 {marker_cls}")) {
+                        span(class="text-gray-500") { (position) }
+                        DisplayError(error=diagnostic.error, rule_id=Some(id))
+                        pre(class="p-1 my-1 overflow-x-auto") {
+                            (excerpt) "\n" (caret)
+                        }
+                    }
+                }
+            },
+        )
+    };
 
     view! { cx,
-        DisplayNode(node=root_node, is_root=true)
+        details(
+            class=if max_severity.get().is_some() { "mb-5 text-sm" } else { "hidden" },
+            open=true,
+        ) {
+            summary(class=summary_cls()) { "Encoding and syntax" }
+            ul(class="my-2") {
+                (items)
+            }
+        }
     }
 }
 
-#[derive(PartialEq, Debug, Clone)]
-enum Namespace {
-    Podcast,
+/// Fetches the text of a feed, without the diagnostics that [`Validate`] shows.
+async fn fetch_feed_text(url: &str, use_proxy: bool) -> Result<(String, String), String> {
+    let url = Url::parse(url).map_err(|e| format!("could not parse the URL ({e})"))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err("URL protocol must be http or https".to_string());
+    }
+
+    let feed_url = url.to_string();
+    let request_url = if use_proxy {
+        format!("{}{}", CORS_PROXY_URL, url)
+    } else {
+        url.to_string()
+    };
+
+    let resp = reqwest_wasm::get(request_url)
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(status.to_string());
+    }
+    let text = resp.text().await.map_err(|e| e.to_string())?;
+    Ok((feed_url, text))
 }
 
-#[derive(PartialEq, Debug, Clone, Default)]
-struct TagName(Option<Namespace>, String);
+/// Analyzes two versions of a feed and shows how the findings differ.
+#[component(inline_props)]
+async fn CompareFeeds<'a, G: Html>(
+    cx: Scope<'a>,
+    old_url: String,
+    new: FeedInput,
+    use_proxy: bool,
+) -> View<G> {
+    let old = fetch_feed_text(&old_url, use_proxy).await;
+    let new = match new {
+        FeedInput::Url(url) => fetch_feed_text(&url, use_proxy).await,
+        // The pasted version is checked against the URL of the old one, so that the findings
+        // of both versions come from the same checks.
+        FeedInput::Source(source) => match &old {
+            Ok((feed_url, _)) => Ok((feed_url.clone(), source)),
+            Err(e) => Err(e.clone()),
+        },
+    };
+    let analyze = |fetched: Result<(String, String), String>| match fetched {
+        Ok((feed_url, text)) => validator::validate_fetched(&text, &feed_url),
+        Err(e) => Err(format!("could not fetch it: {e}")),
+    };
 
-#[derive(PartialEq, Clone, Default)]
-struct Node {
-    name: TagName,
-    children: Vec<Node>,
-    attributes: Vec<(String, Value)>,
-    errors: Vec<Error>,
+    let (old, new) = match (analyze(old), analyze(new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) => {
+            return view! { cx,
+                utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not analyze the old version ({e})"))
+            }
+        }
+        (_, Err(e)) => {
+            return view! { cx,
+                utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not analyze the new version ({e})"))
+            }
+        }
+    };
+
+    view! { cx,
+        DisplayDiff(diffs=validator::diff::diff(&old, &new))
+    }
 }
 
-#[derive(PartialEq, Clone)]
-enum Error {
-    MissingAttribute(String),
-    InvalidAttribute(String, String),
-    InvalidAttributeWithReason(String, String, String),
-    MissingChild(TagName),
-    MultipleChildren(TagName),
-    AttributeExceedsMaxLength(String, String, usize),
-    Custom(String),
-    CustomWithExtraInfo(String, String),
+#[component(inline_props)]
+fn DisplayDiff<G: Html>(cx: Scope, diffs: Vec<NodeDiff>) -> View<G> {
+    let introduced = diffs.iter().map(|d| d.introduced.len()).sum::<usize>();
+    let resolved = diffs.iter().map(|d| d.resolved.len()).sum::<usize>();
+    let introduced_errors = diffs
+        .iter()
+        .flat_map(|d| &d.introduced)
+        .filter(|e| e.severity() == Severity::Error)
+        .count();
+
+    let (type_, msg) = if diffs.is_empty() {
+        (
+            utils::AlertType::Success,
+            "Both versions have the same elements and findings.".to_string(),
+        )
+    } else {
+        let type_ = if introduced_errors > 0 {
+            utils::AlertType::Danger
+        } else if introduced > 0 {
+            utils::AlertType::Warning
+        } else {
+            utils::AlertType::Success
+        };
+        (
+            type_,
+            format!(
+                "{} elements differ. The new version introduces {} findings ({} errors) and resolves {}.",
+                diffs.len(),
+                introduced,
+                introduced_errors,
+                resolved
+            ),
+        )
+    };
+    let diffs = create_signal(cx, diffs);
+
+    view! { cx,
+        div(class="mb-5") {
+            utils::Alert(type_=type_, msg=msg)
+        }
+        ul(class="space-y-3") {
+            Indexed(
+                iterable=diffs,
+                view=|cx, diff| {
+                    let (badge, badge_cls) = match diff.change {
+                        Change::Added => ("added", "text-success-600"),
+                        Change::Removed => ("removed", "text-danger-500"),
+                        Change::Changed => ("changed", "text-gray-500"),
+                    };
+                    let attributes = create_signal(cx, diff.attributes);
+                    let introduced = create_signal(cx, diff.introduced);
+                    let resolved = create_signal(cx, diff.resolved);
+                    view! { cx,
+                        li(class="text-sm") {
+                            div {
+                                span(class=format!("font-bold mr-2 {badge_cls}")) { (badge) }
+                                code(class="break-all") { (diff.path) }
+                            }
+                            ul(class="pl-4 my-1") {
+                                Indexed(
+                                    iterable=attributes,
+                                    view=|cx, (name, old, new)| {
+                                        let old = old.as_ref().map(value_str).unwrap_or_default();
+                                        let new = new.as_ref().map(value_str).unwrap_or_default();
+                                        view! { cx,
+                                            li(class="my-0") {
+                                                code { span(class="attr") { (name) } "=" }
+                                                span(class="line-through text-gray-400") { (old) }
+                                                " → "
+                                                (new)
+                                            }
+                                        }
+                                    },
+                                )
+                                Indexed(
+                                    iterable=introduced,
+                                    view=|cx, error| {
+                                        let marker_cls = severity_marker_cls(error.severity());
+                                        view! { cx,
+                                            li(class=format!("my-0 {marker_cls}")) {
+                                                span(class="font-bold mr-1") { "New:" }
+                                                DisplayError(error=error, rule_id=None)
+                                            }
+                                        }
+                                    },
+                                )
+                                Indexed(
+                                    iterable=resolved,
+                                    view=|cx, error| {
+                                        let error = error.to_string();
+                                        view! { cx,
+                                            li(class="my-0 marker:text-success-600 text-gray-400") {
+                                                span(class="font-bold mr-1 text-success-600") { "Resolved:" }
+                                                span(class="line-through") { (error) }
+                                            }
+                                        }
+                                    },
+                                )
+                            }
+                        }
+                    }
+                },
+            )
+        }
+    }
 }
 
-const NODE_VALUE: &str = "node value";
+fn value_str(value: &Value) -> String {
+    match value {
+        Value::Text(s) => format!("“{s}”"),
+        Value::Object(s) | Value::Url(s) => s.to_string(),
+    }
+}
 
 #[component(inline_props)]
-fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
+fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error, rule_id: Option<String>) -> View<G> {
+    let cls = severity_text_cls(error.severity());
+    let body = error_body(cx, error, cls);
+
+    match rule_id {
+        Some(rule_id) => {
+            let href = format!("/validator/rules/{rule_id}");
+            view! { cx,
+                a(
+                    class="float-right ml-2 text-xs text-gray-400 hover:text-primary-500",
+                    href=href,
+                    title="Learn more about this rule",
+                ) { (rule_id) }
+                (body)
+            }
+        }
+        None => body,
+    }
+}
+
+/// Describes the finding, in the color of `cls`.
+///
+/// A downgraded finding is described like the original one, but in the color of its new severity.
+fn error_body<'a, G: Html>(cx: Scope<'a>, error: Error, cls: &'static str) -> View<G> {
     match error {
         Error::MissingAttribute(attr) => {
             if attr == NODE_VALUE {
                 view! { cx,
-                    div(class="text-danger-500") {
+                    div(class=cls) {
                         "Missing node value"
                     }
                 }
             } else {
                 view! { cx,
-                    div(class="text-danger-500") {
+                    div(class=cls) {
                         "Missing attribute "
                             code(class="attr") { (attr) }
                     }
@@ -373,14 +1148,14 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
         Error::InvalidAttribute(attr, value) => {
             if attr == NODE_VALUE {
                 view! { cx,
-                        div(class="text-danger-500") {
+                        div(class=cls) {
                             "Invalid node value "
                             code { "“" (value) "”" }
                     }
                 }
             } else {
                 view! { cx,
-                        div(class="text-danger-500") {
+                        div(class=cls) {
                             "Attribute "
                                 code(class="attr") { (attr) }
                             " has invalid value "
@@ -392,7 +1167,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
         Error::InvalidAttributeWithReason(attr, value, reason) => {
             if attr == NODE_VALUE {
                 view! { cx,
-                        span(class="text-danger-500") {
+                        span(class=cls) {
                             "Invalid node value "
                             code { "“" (value) "”" }
                     }
@@ -406,7 +1181,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
                 }
             } else {
                 view! { cx,
-                        span(class="text-danger-500") {
+                        span(class=cls) {
                             "Attribute "
                                 code(class="attr") { (attr) }
                             " has invalid value "
@@ -425,7 +1200,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
         }
         Error::MissingChild(tag_name) => {
             view! { cx,
-                    div(class="text-danger-500") {
+                    div(class=cls) {
                         "Missing child "
                         code { "<" (tag_name) ">" }
                 }
@@ -433,7 +1208,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
         }
         Error::MultipleChildren(tag_name) => {
             view! { cx,
-                    div(class="text-danger-500") {
+                    div(class=cls) {
                         "Only one child "
                         code { "<" (tag_name) ">" }
                     " is allowed"
@@ -443,7 +1218,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
         Error::AttributeExceedsMaxLength(attr, value, max_len) => {
             if attr == NODE_VALUE {
                 view! { cx,
-                        div(class="text-danger-500") {
+                        div(class=cls) {
                             "Node value "
                             code { "“" (value) "”" }
                         " exceeds maximum length of "
@@ -453,7 +1228,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
                 }
             } else {
                 view! { cx,
-                        div(class="text-danger-500") {
+                        div(class=cls) {
                             "Attribute "
                                 code(class="attr") { (attr) }
                             " exceeds maximum length of "
@@ -463,12 +1238,19 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
                 }
             }
         }
-        Error::Custom(msg) => {
+        Error::Custom(_, msg) => {
             view! { cx,
-                div(class="text-danger-500", dangerously_set_inner_html=msg.as_str()) {}
+                div(class=cls, dangerously_set_inner_html=msg.as_str()) {}
             }
         }
-        Error::CustomWithExtraInfo(msg, extra_info) => {
+        Error::Downgraded(_, error) => {
+            let body = error_body(cx, *error, cls);
+            view! { cx,
+                (body)
+                span(class="text-xs text-gray-400") { "(downgraded by your rule settings)" }
+            }
+        }
+        Error::CustomWithExtraInfo(_, msg, extra_info) => {
             let show_extra_info = create_signal(cx, false);
 
             let extra_info = move || {
@@ -489,7 +1271,7 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
 
             view! { cx,
                 div {
-                div(class="text-danger-500", dangerously_set_inner_html=msg.as_str()) {}
+                div(class=cls, dangerously_set_inner_html=msg.as_str()) {}
                 }
                 div {
                     span(
@@ -504,1750 +1286,770 @@ fn DisplayError<'a, G: Html>(cx: Scope<'a>, error: Error) -> View<G> {
     }
 }
 
-impl std::fmt::Display for TagName {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TagName(Some(Namespace::Podcast), x) => write!(f, "podcast:{}", x),
-            TagName(None, x) => write!(f, "{}", x),
-        }
+fn tab_cls(selected: bool) -> &'static str {
+    if selected {
+        "btn-base btn-primary rounded-xl py-1"
+    } else {
+        "btn-base rounded-xl py-1 text-primary-500"
     }
 }
 
-impl Node {
-    fn descendants_have_errors(&self) -> bool {
-        if !self.errors.is_empty() {
-            return true;
-        }
-
-        for child in &self.children {
-            if child.descendants_have_errors() {
-                return true;
-            }
-        }
-
-        false
+pub(super) fn severity_text_cls(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "text-danger-500",
+        Severity::Warning => "text-warning-700",
+        Severity::Info => "text-primary-500",
     }
+}
 
-    fn descendants_have_podcast_tags(&self) -> bool {
-        if self.name.0 == Some(Namespace::Podcast) {
-            return true;
-        }
-
-        for child in &self.children {
-            if child.descendants_have_podcast_tags() {
-                return true;
-            }
-        }
-
-        false
+fn severity_marker_cls(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "marker:text-danger-500",
+        Severity::Warning => "marker:text-warning-700",
+        Severity::Info => "marker:text-primary-500",
     }
 }
 
+/// Shows the findings of the report with the rule settings applied.
+///
+/// `other_errors` are the findings about the response and the source, which are shown elsewhere
+/// but are listed in the rule settings and counted when the settings hide them.
 #[component(inline_props)]
-fn DisplayNode<'a, G: Html>(cx: Scope<'a>, node: Node, is_root: bool) -> View<G> {
-    let children = create_signal(cx, node.children.clone());
-    let errors = create_signal(cx, node.errors.clone());
-    let attributes = create_signal(cx, node.attributes.clone());
-    let have_nested_errors = node.descendants_have_errors();
-    let have_podcast_tags = node.descendants_have_podcast_tags();
-    let name_cls = if have_nested_errors {
-        "text-danger-500"
-    } else {
-        ""
-    };
-    let details_cls = if is_root { "overflow-x-auto" } else { "" };
-
-    view! { cx,
-            (match (is_root, have_podcast_tags, have_nested_errors) {
-                (true, false, _) => view! { cx,
-                    div(class="mb-5") {
-                        utils::Alert(type_=utils::AlertType::Info, msg="No podcast namespace tags found.".to_string())
-                    }
-                },
-                (true, true, false) => view! { cx,
-                    div(class="mb-5") {
-                        utils::Alert(type_=utils::AlertType::Success, msg="Our analysis has not found any errors in the podcast namespace tags.".to_string())
-                    }
-                },
-                _ => view! { cx, },
-            })
-
-            details(class=details_cls, open=have_nested_errors) {
-            summary(class=name_cls) {
-                code(class="font-bold") { "<"(node.name)">" }
-    }
-    div(class="pl-1") {
-                    div(class="pl-2 md:pl-4 border-l-2 border-gray-200") {
-                        ul(class="text-sm my-0") {
-                            Indexed(
-                                iterable=errors,
-                                view=|cx, x| view! { cx,
-                                    li(class="my-0 marker:text-danger-500") { DisplayError(error=x) }
-                                },
-                            )
-                                Indexed(
-                                    iterable=attributes,
-                                    view=|cx, x| view! { cx,
-                                        li(class="my-0") {
-                                            code { span(class="attr") { (x.0) }  "=" }
-                                            (match &x.1 {
-                                                Value::Text(s) => {
-                                                    let s = s.to_string();
-                                                    view! { cx, "“" (s) "”" }
-                                                },
-                                                Value::Object(s) => {
-                                                    let s = s.to_string();
-                                                    view! { cx, span(class="italic") { (s) } }
-                                                },
-                                                Value::Url(s) => {
-                                                    let s = s.to_string();
-                                                    if s.starts_with("http") {
-                                                        view! { cx, utils::Link(url=s.to_string(), text=s.to_string(), new_tab=true) }
-                                                    } else {
-                                                        view! { cx, span(class="italic") { (s) } }
-                                                    }
-                                                }
-                                            }
-                                            )
-                                        }
-                                    },
-                                )
-                        }
-
-                        Indexed(
-                            iterable=children,
-                            view=|cx, x| view! { cx,
-                                DisplayNode(node=x, is_root=false)
-                            },
-                        )
-                    }
-                }
-            }
-        }
-}
-
-fn analyze_rss(rss: &badpod::Rss) -> Node {
-    let mut errors = Vec::new();
-    let mut children = Vec::new();
-
-    for channel in &rss.channel {
-        children.push(analyze_channel(channel));
-    }
-    match rss.channel.len() {
-        0 => errors.push(Error::MissingChild(TagName(None, "channel".to_string()))),
-        1 => (),
-        _ => errors.push(Error::MultipleChildren(TagName(
-            None,
-            "channel".to_string(),
-        ))),
-    }
-
-    Node {
-        name: TagName(None, "rss".to_string()),
-        children,
-        ..Default::default()
-    }
-}
-
-fn analyze_channel(channel: &badpod::Channel) -> Node {
-    let mut errors = Vec::new();
-    let mut children = Vec::new();
-
-    for title in &channel.title {
-        children.push(analyze_title(title.to_string()));
-    }
-    match channel.title.len() {
-        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
-        1 => {}
-        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
-    }
-
-    for guid in &channel.podcast_guid {
-        children.push(analyze_podcast_guid(guid));
-    }
-    if channel.podcast_guid.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "guid".to_string(),
-        )));
-    }
-
-    for medium in &channel.podcast_medium {
-        children.push(analyze_podcast_medium(medium));
-    }
-    if channel.podcast_medium.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "medium".to_string(),
-        )));
-    }
-
-    for txt in &channel.podcast_txt {
-        children.push(analyze_podcast_txt(txt));
-    }
-
-    for block in &channel.podcast_block {
-        children.push(analyze_podcast_block(block));
-    }
-
-    for locked in &channel.podcast_locked {
-        children.push(analyze_podcast_locked(locked));
-    }
-    if channel.podcast_locked.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "locked".to_string(),
-        )));
-    }
-
-    for funding in &channel.podcast_funding {
-        children.push(analyze_podcast_funding(funding));
-    }
-
-    for location in &channel.podcast_location {
-        children.push(analyze_podcast_location(location));
-    }
-    if channel.podcast_location.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "location".to_string(),
-        )));
-    }
-
-    for person in &channel.podcast_person {
-        children.push(analyze_podcast_person(person));
-    }
-
-    for trailer in &channel.podcast_trailer {
-        children.push(analyze_podcast_trailer(trailer));
-    }
-
-    for license in &channel.podcast_license {
-        children.push(analyze_podcast_license(license));
-    }
-    if channel.podcast_license.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "license".to_string(),
-        )));
-    }
-
-    for v4v_value in &channel.podcast_value {
-        children.push(analyze_podcast_value(v4v_value));
-    }
-
-    for images in &channel.podcast_images {
-        children.push(analyze_podcast_images(images));
-    }
-    if channel.podcast_images.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "images".to_string(),
-        )));
-    }
-
-    for item in &channel.item {
-        children.push(analyze_item(item));
-    }
-
-    for live_item in &channel.podcast_live_item {
-        children.push(analyze_podcast_live_item(live_item));
-    }
-
-    Node {
-        name: TagName(None, "channel".to_string()),
-        children,
-        errors,
-        attributes: Vec::new(),
-    }
-}
-
-fn analyze_item(item: &badpod::Item) -> Node {
-    let mut children = Vec::new();
-    let mut errors = Vec::new();
-
-    for title in &item.title {
-        children.push(analyze_title(title.to_string()));
-    }
-    match item.title.len() {
-        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
-        1 => {}
-        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
-    }
-
-    for v4v_value in &item.podcast_value {
-        children.push(analyze_podcast_value(v4v_value));
-    }
-
-    for person in &item.podcast_person {
-        children.push(analyze_podcast_person(person));
-    }
-
-    for location in &item.podcast_location {
-        children.push(analyze_podcast_location(location));
-    }
-    if item.podcast_location.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "location".to_string(),
-        )));
-    }
-
-    for images in &item.podcast_images {
-        children.push(analyze_podcast_images(images));
-    }
-    if item.podcast_images.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "images".to_string(),
-        )));
-    }
-
-    for txt in &item.podcast_txt {
-        children.push(analyze_podcast_txt(txt));
-    }
-
-    for transcript in &item.podcast_transcript {
-        children.push(analyze_podcast_transcript(transcript));
-    }
-
-    for chapters in &item.podcast_chapters {
-        children.push(analyze_podcast_chapters(chapters));
-    }
-    if item.podcast_chapters.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "chapters".to_string(),
-        )));
-    }
-
-    for soundbite in &item.podcast_soundbite {
-        children.push(analyze_podcast_soundbite(soundbite));
-    }
-
-    for season in &item.podcast_season {
-        children.push(analyze_podcast_season(season));
-    }
-    if item.podcast_season.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "season".to_string(),
-        )));
-    }
-
-    for episode in &item.podcast_episode {
-        children.push(analyze_podcast_episode(episode));
-    }
-    if item.podcast_episode.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "episode".to_string(),
-        )));
-    }
-
-    for license in &item.podcast_license {
-        children.push(analyze_podcast_license(license));
-    }
-    if item.podcast_license.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "license".to_string(),
-        )));
-    }
-
-    for alternate_enclosure in &item.podcast_alternate_enclosure {
-        children.push(analyze_podcast_alternate_enclosure(alternate_enclosure));
-    }
-
-    for social_interact in &item.podcast_social_interact {
-        children.push(analyze_podcast_social_interact(social_interact));
-    }
-
-    Node {
-        name: TagName(None, "item".to_string()),
-        children,
-        errors,
-        attributes: Vec::new(),
-    }
-}
-
-fn analyze_podcast_live_item(item: &badpod::podcast::LiveItem) -> Node {
-    let mut children = Vec::new();
-    let mut attributes = Vec::new();
-    let mut errors = Vec::new();
-
-    match &item.status {
-        Some(badpod::podcast::LiveItemStatus::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "status".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ))
-        }
-        Some(s) => attributes.push(("status".to_string(), Value::Object(s.to_string()))),
-        None => errors.push(Error::MissingAttribute("status".to_string())),
-    }
-
-    match &item.start {
-        Some(badpod::DateTime::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "start".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ))
-        }
-        Some(t) => attributes.push(("start".to_string(), Value::Object(t.to_string()))),
-        None => errors.push(Error::MissingAttribute("start".to_string())),
-    }
-
-    match &item.end {
-        Some(badpod::DateTime::Other((s, reason))) => errors.push(
-            Error::InvalidAttributeWithReason("end".to_string(), s.to_string(), reason.to_string()),
-        ),
-        Some(t) => attributes.push(("end".to_string(), Value::Object(t.to_string()))),
-        None => errors.push(Error::MissingAttribute("end".to_string())),
-    }
-
-    for title in &item.title {
-        children.push(analyze_title(title.to_string()));
-    }
-    match item.title.len() {
-        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
-        1 => {}
-        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
-    }
-
-    for content_link in &item.podcast_content_link {
-        children.push(analyze_podcast_content_link(content_link));
-    }
-    if item.podcast_content_link.is_empty() {
-        errors.push(Error::MissingChild(TagName(
-            Some(Namespace::Podcast),
-            "contentLink".to_string(),
-        )));
-    }
-
-    for v4v_value in &item.podcast_value {
-        children.push(analyze_podcast_value(v4v_value));
-    }
-
-    for person in &item.podcast_person {
-        children.push(analyze_podcast_person(person));
-    }
-
-    for location in &item.podcast_location {
-        children.push(analyze_podcast_location(location));
-    }
-    if item.podcast_location.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "location".to_string(),
-        )));
-    }
-
-    for images in &item.podcast_images {
-        children.push(analyze_podcast_images(images));
-    }
-    if item.podcast_images.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "images".to_string(),
-        )));
-    }
-
-    for txt in &item.podcast_txt {
-        children.push(analyze_podcast_txt(txt));
-    }
-
-    for transcript in &item.podcast_transcript {
-        children.push(analyze_podcast_transcript(transcript));
-    }
-
-    for chapters in &item.podcast_chapters {
-        children.push(analyze_podcast_chapters(chapters));
-    }
-    if item.podcast_chapters.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "chapters".to_string(),
-        )));
-    }
-
-    for soundbite in &item.podcast_soundbite {
-        children.push(analyze_podcast_soundbite(soundbite));
-    }
-
-    for season in &item.podcast_season {
-        children.push(analyze_podcast_season(season));
-    }
-    if item.podcast_season.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "season".to_string(),
-        )));
-    }
-
-    for episode in &item.podcast_episode {
-        children.push(analyze_podcast_episode(episode));
-    }
-    if item.podcast_episode.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "episode".to_string(),
-        )));
-    }
-
-    for license in &item.podcast_license {
-        children.push(analyze_podcast_license(license));
-    }
-    if item.podcast_license.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "license".to_string(),
-        )));
-    }
-
-    for alternate_enclosure in &item.podcast_alternate_enclosure {
-        children.push(analyze_podcast_alternate_enclosure(alternate_enclosure));
-    }
-
-    for social_interact in &item.podcast_social_interact {
-        children.push(analyze_podcast_social_interact(social_interact));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "liveItem".to_string()),
-        children,
-        errors,
-        attributes,
-    }
-}
-
-fn analyze_title(title: String) -> Node {
-    Node {
-        name: TagName(None, "title".to_string()),
-        attributes: vec![(NODE_VALUE.to_string(), Value::Text(title.to_string()))],
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_value(v4v_value: &badpod::podcast::Value) -> Node {
-    let mut children = Vec::new();
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    for recipient in &v4v_value.value_recipient {
-        children.push(analyze_podcast_value_recipient(recipient));
-    }
-    if v4v_value.value_recipient.is_empty() {
-        errors.push(Error::MissingChild(TagName(
-            Some(Namespace::Podcast),
-            "valueRecipient".to_string(),
-        )));
-    }
-
-    match &v4v_value.type_ {
-        Some(badpod::podcast::ValueType::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "type".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ))
-        }
-        Some(type_) => {
-            attributes.push(("type".to_string(), Value::Object(type_.to_string())));
-        }
-        None => {
-            errors.push(Error::MissingAttribute("type".to_string()));
-        }
-    }
-
-    match &v4v_value.method {
-        Some(badpod::podcast::ValueMethod::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "method".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ))
-        }
-        Some(method) => {
-            attributes.push(("method".to_string(), Value::Object(method.to_string())));
-        }
-        None => {
-            errors.push(Error::MissingAttribute("method".to_string()));
-        }
-    }
-
-    match &v4v_value.suggested {
-        Some(badpod::Float::Ok(f)) => {
-            attributes.push(("suggested".to_string(), Value::Object(f.to_string())));
-        }
-        Some(badpod::Float::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "suggested".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {}
-    };
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "value".to_string()),
-        children,
-        errors,
-        attributes,
-    }
-}
-
-fn analyze_podcast_value_recipient(recipient: &badpod::podcast::ValueRecipient) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(type_) = &recipient.type_ {
-        match type_ {
-            badpod::podcast::ValueRecipientType::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "type".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
-            }
-        }
-    }
-
-    if let Some(address) = &recipient.address {
-        attributes.push(("address".to_string(), Value::Text(address.to_string())));
-    } else {
-        errors.push(Error::MissingAttribute("address".to_string()));
-    }
-
-    if let Some(split) = &recipient.split {
-        match split {
-            badpod::Integer::Ok(i) => {
-                attributes.push(("split".to_string(), Value::Object(i.to_string())));
-            }
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "split".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    if let Some(name) = &recipient.name {
-        attributes.push(("name".to_string(), Value::Text(name.to_string())));
-    }
-
-    if let Some(custom_key) = &recipient.custom_key {
-        attributes.push(("customKey".to_string(), Value::Text(custom_key.to_string())));
-    }
-
-    if let Some(custom_value) = &recipient.custom_value {
-        attributes.push((
-            "customValue".to_string(),
-            Value::Text(custom_value.to_string()),
-        ));
-    }
-
-    match (&recipient.custom_key, &recipient.custom_value) {
-        (Some(_), None) => {
-            errors.push(Error::MissingAttribute("customValue".to_string()));
-        }
-        (None, Some(_)) => {
-            errors.push(Error::MissingAttribute("customKey".to_string()));
-        }
-        _ => {}
-    }
-
-    if let Some(fee) = &recipient.fee {
-        match fee {
-            badpod::Bool::Ok(b) => {
-                attributes.push(("fee".to_string(), Value::Object(b.to_string())));
-            }
-            badpod::Bool::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "fee".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
+fn DisplayReport<'a, G: Html>(
+    cx: Scope<'a>,
+    report: Report,
+    feed_url: Option<String>,
+    source: String,
+    other_errors: Vec<Error>,
+    settings: &'a Signal<RuleSettings>,
+    storage_error: &'a ReadSignal<Option<String>>,
+) -> View<G> {
+    let selected_span = create_signal(cx, None::<Span>);
+    let min_severity_str = create_signal(cx, Severity::Info.to_string());
+    let min_severity = create_memo(cx, || {
+        min_severity_str.get().parse().unwrap_or(Severity::Info)
+    });
 
-    Node {
-        name: TagName(Some(Namespace::Podcast), "valueRecipient".to_string()),
-        children: vec![],
-        errors,
-        attributes,
-    }
-}
+    let mut rules = Vec::new();
+    collect_rules(
+        &Node {
+            errors: other_errors.clone(),
+            ..Default::default()
+        },
+        &mut rules,
+    );
+    collect_rules(&report.root, &mut rules);
+    let rules = create_signal(cx, rules);
+
+    let has_podcast_tags = report.has_podcast_tags();
+    let report = create_ref(cx, report);
+    let feed_url = create_ref(cx, feed_url);
+    let source = create_ref(cx, source);
+    let applied = create_memo(cx, move || {
+        let mut applied = report.clone();
+        let suppressed = settings.get().apply(&mut applied)
+            + other_errors
+                .iter()
+                .filter(|error| {
+                    settings
+                        .get()
+                        .apply_to_error(&Node::default(), (*error).clone())
+                        .is_none()
+                })
+                .count();
+        (applied, suppressed)
+    });
 
-fn analyze_podcast_location(location: &badpod::podcast::Location) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(value) = &location.value {
-        if value.len() > 128 {
-            errors.push(Error::AttributeExceedsMaxLength(
-                NODE_VALUE.to_string(),
-                value.to_string(),
-                128,
-            ));
+    view! { cx,
+        (if has_podcast_tags {
+            view! { cx, }
         } else {
-            attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
-        }
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    if let Some(geo) = &location.geo {
-        match geo {
-            badpod::podcast::Geo::Ok {
-                latitude,
-                longitude,
-                altitude,
-                uncertainty,
-            } => {
-                let mut geo_str = format!("{{ latitude: {}, longitude: {}", latitude, longitude);
-                if let Some(altitude) = altitude {
-                    geo_str.push_str(format!(", altitude: {}", altitude).as_str());
-                }
-                if let Some(uncertainty) = uncertainty {
-                    geo_str.push_str(format!(", uncertainty: {}", uncertainty).as_str());
-                }
-                geo_str.push_str(" }");
-                attributes.push(("geo".to_string(), Value::Object(geo_str)));
-            }
-            badpod::podcast::Geo::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "geo".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    if let Some(osm) = &location.osm {
-        match osm {
-            badpod::podcast::Osm::Ok {
-                type_,
-                id,
-                revision,
-            } => {
-                let mut osm_str = format!("{{ type: {:?}, id: {}", type_, id);
-                if let Some(revision) = revision {
-                    osm_str.push_str(format!(", revision: {}", revision).as_str());
+            view! { cx,
+                div(class="mb-5") {
+                    utils::Alert(type_=utils::AlertType::Info, msg="No podcast namespace tags found.".to_string())
                 }
-                osm_str.push_str(" }");
-                attributes.push(("osm".to_string(), Value::Object(osm_str)));
             }
-            badpod::podcast::Osm::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "osm".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "location".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_guid(guid: &badpod::podcast::Guid) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match guid {
-        badpod::podcast::Guid::Ok(guid) => {
-            attributes.push((NODE_VALUE.to_string(), Value::Text(guid.to_string())));
-        }
-        badpod::podcast::Guid::Other((s, reason)) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "guid".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_medium(medium: &badpod::podcast::Medium) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match medium {
-        badpod::podcast::Medium::Other((s, reason)) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        _ => {
-            attributes.push((NODE_VALUE.to_string(), Value::Object(medium.to_string())));
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "medium".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_txt(txt: &badpod::podcast::Txt) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(value) = &txt.value {
-        attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    match &txt.purpose {
-        Some(badpod::podcast::TxtPurpose::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "purpose".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        Some(purpose) => {
-            attributes.push(("purpose".to_string(), Value::Object(purpose.to_string())));
-        }
-        None => {}
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "txt".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_block(block: &badpod::podcast::Block) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &block.value {
-        Some(badpod::Bool::Ok(b)) => {
-            attributes.push((NODE_VALUE.to_string(), Value::Object(b.to_string())));
-        }
-        Some(badpod::Bool::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {
-            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-        }
-    }
-
-    if let Some(id) = &block.id {
-        match id {
-            badpod::podcast::Service::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "id".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("id".to_string(), Value::Object(id.to_string())));
-            }
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "block".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_locked(locked: &badpod::podcast::Locked) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &locked.value {
-        Some(badpod::Bool::Ok(b)) => {
-            attributes.push((NODE_VALUE.to_string(), Value::Object(b.to_string())));
-        }
-        Some(badpod::Bool::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {
-            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-        }
-    }
-
-    if let Some(owner) = &locked.owner {
-        attributes.push(("owner".to_string(), Value::Text(owner.to_string())));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "locked".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_funding(funding: &badpod::podcast::Funding) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(value) = &funding.value {
-        if value.len() > 128 {
-            errors.push(Error::AttributeExceedsMaxLength(
-                NODE_VALUE.to_string(),
-                value.to_string(),
-                128,
-            ));
-        } else {
-            attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
-        }
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    if let Some(url) = &funding.url {
-        match url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("url".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "url".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("url".to_string()));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "funding".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
+        })
 
-fn analyze_podcast_person(person: &badpod::podcast::Person) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(name) = &person.value {
-        if name.len() > 128 {
-            errors.push(Error::AttributeExceedsMaxLength(
-                NODE_VALUE.to_string(),
-                name.to_string(),
-                128,
-            ));
-        } else {
-            attributes.push((NODE_VALUE.to_string(), Value::Text(name.to_string())));
-        }
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    if let Some(group) = &person.group {
-        match group {
-            badpod::podcast::PersonGroup::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "group".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("group".to_string(), Value::Object(group.to_string())));
-            }
-        }
-    }
-
-    if let Some(role) = &person.role {
-        match role {
-            badpod::podcast::PersonRole::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "role".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("role".to_string(), Value::Object(role.to_string())));
-            }
-        }
-    }
-
-    if let Some(image) = &person.img {
-        match image {
-            badpod::Url::Ok(url) => {
-                attributes.push(("img".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "img".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    if let Some(href) = &person.href {
-        match href {
-            badpod::Url::Ok(url) => {
-                attributes.push(("href".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "href".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "person".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_trailer(trailer: &badpod::podcast::Trailer) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(tile) = &trailer.value {
-        if tile.len() > 128 {
-            errors.push(Error::AttributeExceedsMaxLength(
-                NODE_VALUE.to_string(),
-                tile.to_string(),
-                128,
-            ));
-        } else {
-            attributes.push((NODE_VALUE.to_string(), Value::Text(tile.to_string())));
-        }
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    if let Some(url) = &trailer.url {
-        match url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("url".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "url".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("url".to_string()));
-    }
-
-    if let Some(pub_date) = &trailer.pub_date {
-        match pub_date {
-            badpod::DateTime::Ok(dt) => {
-                attributes.push(("pubDate".to_string(), Value::Object(dt.to_string())));
-            }
-            badpod::DateTime::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "pubDate".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    if let Some(length) = &trailer.length {
-        match length {
-            badpod::Integer::Ok(i) => {
-                attributes.push(("length".to_string(), Value::Object(i.to_string())));
-            }
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "length".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    if let Some(mimetype) = &trailer.type_ {
-        match mimetype {
-            badpod::MimeEnclosure::Other((s, _)) => {
-                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
-            }
-            _ => {
-                attributes.push(("type".to_string(), Value::Text(mimetype.to_string())));
-            }
-        }
-    }
-
-    if let Some(season) = &trailer.season {
-        match season {
-            badpod::Integer::Ok(i) => {
-                attributes.push(("season".to_string(), Value::Object(i.to_string())));
-            }
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "season".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "trailer".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_license(license: &badpod::podcast::License) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(url) = &license.url {
-        match url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("url".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "url".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
-
-    match &license.value {
-        Some(value) => {
-            match value {
-                badpod::podcast::LicenseType::Other((s, _)) => {
-                    if s.len() > 128 {
-                        errors.push(Error::AttributeExceedsMaxLength(
-                            NODE_VALUE.to_string(),
-                            s.to_string(),
-                            128,
-                        ));
-                    } else {
-                        attributes.push((NODE_VALUE.to_string(), Value::Text(s.to_string())));
-                    }
-                    if license.url.is_none() {
-                        errors.push(Error::MissingAttribute("url".to_string()));
-                    }
-                }
-                _ => {
-                    attributes.push((NODE_VALUE.to_string(), Value::Object(value.to_string())));
-                }
+        ({
+            let applied = applied.get();
+            let (applied, suppressed) = &*applied;
+            let suppressed_str = match *suppressed {
+                0 => String::new(),
+                1 => " 1 finding is hidden by your rule settings.".to_string(),
+                n => format!(" {n} findings are hidden by your rule settings."),
             };
-        }
-        None => {
-            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "license".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_images(images: &badpod::podcast::Images) -> Node {
-    let mut attributes = Vec::new();
-    let mut errors = Vec::new();
-
-    match &images.srcset {
-        badpod::podcast::ImageSrcSet::Ok(image_data) => {
-            let mut img_strs = Vec::new();
-            for (url, width) in image_data {
-                img_strs.push(format!("{{ url: \"{}\", width: {} }}", url, width));
-            }
-            let value = format!("[ {} ]", img_strs.join(", "));
-            attributes.push(("srcset".to_string(), Value::Object(value)));
-        }
-        badpod::podcast::ImageSrcSet::Other((s, reason)) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "srcset".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "images".to_string()),
-        attributes,
-        errors,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_transcript(transcript: &badpod::podcast::Transcript) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(url) = &transcript.url {
-        match url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("url".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "url".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("url".to_string()));
-    }
-
-    if let Some(type_) = &transcript.type_ {
-        match type_ {
-            badpod::MimeTranscript::ApplicationSrt => {
-                errors.push(Error::CustomWithExtraInfo(
-                    "\"<code>application/srt</code>\" in attribute <code class=\"font-bold\">type</code> is not a valid mime type.".to_string(),
-                    "<a class=\"link\" href=\"https://github.com/Podcastindex-org/podcast-namespace/pull/331\" target=\"_blank\" rel=\"noopener noreferrer\">On February 3, 2022</a>, the recognized alternative for SubRip files in the podcast namespace specification became \"<code>application/x-subrip</code>\". However, keep in mind that although podcast players like Podverse and Podcast Addict have updated their codebases, some other apps may still only recognize \"<code>application/srt</code>\" at this time."
-                        .to_string(),
-                ));
-            }
-            badpod::MimeTranscript::Other((s, _)) => {
-                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
-            }
-            _ => {
-                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("type".to_string()));
-    }
-
-    if let Some(language) = &transcript.language {
-        match language {
-            badpod::Language::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "language".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("language".to_string(), Value::Object(language.to_string())));
+            match applied.root.max_severity() {
+                None if has_podcast_tags || !suppressed_str.is_empty() => {
+                    let msg = format!("Our analysis has not found any errors in the feed.{suppressed_str}");
+                    view! { cx,
+                        div(class="mb-5") {
+                            utils::Alert(type_=utils::AlertType::Success, msg=msg)
+                        }
+                    }
+                },
+                None => view! { cx, },
+                Some(max_severity) => {
+                    let type_ = match max_severity {
+                        Severity::Error => utils::AlertType::Danger,
+                        Severity::Warning => utils::AlertType::Warning,
+                        Severity::Info => utils::AlertType::Info,
+                    };
+                    let msg = format!("Our analysis has found {}.{suppressed_str}", counts_str(applied));
+                    view! { cx,
+                        div(class="mb-5") {
+                            utils::Alert(type_=type_, msg=msg)
+                        }
+                        div(class="flex flex-row items-center mb-3 text-sm") {
+                            label(class="mr-3", for="min-severity") { "Show" }
+                            select(
+                                id="min-severity",
+                                class="input-text-base rounded-xl w-auto py-1 text-sm",
+                                bind:value=min_severity_str,
+                            ) {
+                                option(value="info") { "all findings" }
+                                option(value="warning") { "errors and warnings" }
+                                option(value="error") { "errors only" }
+                            }
+                        }
+                    }
+                }
             }
-        }
-    }
+        })
 
-    if let Some(rel) = &transcript.rel {
-        match rel {
-            badpod::podcast::TranscriptRel::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "rel".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("rel".to_string(), Value::Object(rel.to_string())));
+        (if rules.get().is_empty() {
+            view! { cx, }
+        } else {
+            view! { cx, DisplayRuleSettings(rules=rules, settings=settings) }
+        })
+        (if let Some(e) = storage_error.get().as_ref() {
+            view! { cx,
+                div(class="mb-3") {
+                    utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not save the rule settings ({e})"))
+                }
             }
-        }
+        } else {
+            view! { cx, }
+        })
+
+        ({
+            let summary = summary::summarize(&applied.get().0, source);
+            view! { cx, DisplaySummary(summary=summary, selected_span=selected_span) }
+        })
+        DisplayAdoption(adoption=adoption::adoption(report))
+        ({
+            let root = applied.get().0.root.clone();
+            view! { cx, DisplayNode(node=root, is_root=true, min_severity=min_severity, selected_span=selected_span, source=source) }
+        })
+        DisplaySource(source=source.clone(), selected_span=selected_span)
+        ({
+            let report = applied.get().0.clone();
+            view! { cx, DownloadReport(report=report, feed_url=feed_url.clone(), source=source) }
+        })
     }
+}
 
-    Node {
-        name: TagName(Some(Namespace::Podcast), "transcript".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
+/// Shows an overview of the feed and of where the findings are.
+#[component(inline_props)]
+fn DisplaySummary<'a, G: Html>(
+    cx: Scope<'a>,
+    summary: Summary,
+    selected_span: &'a Signal<Option<Span>>,
+) -> View<G> {
+    let items = summary.items;
+    let mut rows = vec![
+        ("Items".to_string(), items.to_string()),
+        ("Live items".to_string(), summary.live_items.to_string()),
+        ("Size".to_string(), transport::format_size(summary.size)),
+    ];
+    if let Some((first, last)) = summary.date_range {
+        rows.push((
+            "Published".to_string(),
+            format!(
+                "{} – {}",
+                first.format("%b %-d, %Y"),
+                last.format("%b %-d, %Y")
+            ),
+        ));
     }
-}
+    for (label, count) in &summary.features {
+        rows.push((format!("Items with {label}"), share_str(*count, items)));
+    }
+    let rows = View::new_fragment(
+        rows.into_iter()
+            .map(|(name, value)| {
+                view! { cx,
+                    tr {
+                        td(class="pr-3 text-gray-500") { (name) }
+                        td { (value) }
+                    }
+                }
+            })
+            .collect(),
+    );
 
-fn analyze_podcast_chapters(chapters: &badpod::podcast::Chapters) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
+    let findings = if summary.findings_by_tag.is_empty() {
+        view! { cx, }
+    } else {
+        let tags = View::new_fragment(
+            summary
+                .findings_by_tag
+                .into_iter()
+                .map(|(name, count)| {
+                    view! { cx,
+                        li(class="my-0") {
+                            code { "<" (name) ">" } " " (count)
+                        }
+                    }
+                })
+                .collect(),
+        );
+        view! { cx,
+            p(class="mb-0") { "Findings by element:" }
+            ul(class="mt-0 columns-2 md:columns-3") { (tags) }
+        }
+    };
 
-    if let Some(url) = &chapters.url {
-        match url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("url".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "url".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
+    let jump = match summary.first_error {
+        Some(span) => {
+            let text = format!("Jump to the first error (line {})", span.line);
+            view! { cx,
+                button(
+                    class="text-primary-500 hover:underline",
+                    type="button",
+                    on:click=move |_| selected_span.set(Some(span)),
+                ) { (text) }
             }
         }
-    } else {
-        errors.push(Error::MissingAttribute("url".to_string()));
-    }
+        None => view! { cx, },
+    };
 
-    if let Some(type_) = &chapters.type_ {
-        match type_ {
-            badpod::MimeChapters::Other((s, _)) => {
-                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
-            }
-            _ => {
-                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+    view! { cx,
+        details(class="mb-5 text-sm", open=true) {
+            summary(class="font-bold") { "Summary" }
+            table(class="w-auto my-2") {
+                tbody {
+                    (rows)
+                }
             }
+            (findings)
+            (jump)
         }
-    } else {
-        errors.push(Error::MissingAttribute("type".to_string()));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "chapters".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
     }
 }
 
-fn analyze_podcast_soundbite(soundbite: &badpod::podcast::Soundbite) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &soundbite.start_time {
-        Some(badpod::Float::Ok(f)) => {
-            attributes.push(("startTime".to_string(), Value::Object(f.to_string())));
-        }
-        Some(badpod::Float::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "startTime".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {
-            errors.push(Error::MissingAttribute("startTime".to_string()));
-        }
-    }
+/// Shows which tags of the podcast namespace the feed uses, phase by phase.
+#[component(inline_props)]
+fn DisplayAdoption<G: Html>(cx: Scope, adoption: Adoption) -> View<G> {
+    let items = adoption.items;
+    let phases = View::new_fragment(
+        adoption
+            .phases
+            .into_iter()
+            .map(|phase| {
+                let used = phase.tags.iter().filter(|tag| tag.is_used()).count();
+                let heading = format!("Phase {}: {used} of {} tags used", phase.number, phase.tags.len());
+                let rows = View::new_fragment(
+                    phase
+                        .tags
+                        .into_iter()
+                        .map(|tag| {
+                            let name_cls = if tag.is_used() { "" } else { "text-gray-400" };
+                            let (name, spec_url) = (tag.name, tag.spec_url);
+                            let channel = match tag.in_channel {
+                                Some(true) => "used",
+                                Some(false) => "absent",
+                                None => "",
+                            };
+                            let in_items = match tag.in_items {
+                                Some(count) => share_str(count, items),
+                                None => String::new(),
+                            };
+                            view! { cx,
+                                tr {
+                                    td(class=format!("pr-3 {name_cls}")) {
+                                        a(href=spec_url, target="_blank", rel="noopener noreferrer") {
+                                            code { "<podcast:" (name) ">" }
+                                        }
+                                    }
+                                    td(class="pr-3") { (channel) }
+                                    td { (in_items) }
+                                }
+                            }
+                        })
+                        .collect(),
+                );
+                view! { cx,
+                    p(class="mb-1 font-bold") { (heading) }
+                    table(class="w-auto mt-0") {
+                        thead {
+                            tr {
+                                th(class="pr-3 text-left font-normal text-gray-500") { "Tag" }
+                                th(class="pr-3 text-left font-normal text-gray-500") { "Channel" }
+                                th(class="text-left font-normal text-gray-500") { "Items" }
+                            }
+                        }
+                        tbody {
+                            (rows)
+                        }
+                    }
+                }
+            })
+            .collect(),
+    );
 
-    match &soundbite.duration {
-        Some(badpod::Float::Ok(f)) => {
-            attributes.push(("duration".to_string(), Value::Object(f.to_string())));
-        }
-        Some(badpod::Float::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "duration".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {
-            errors.push(Error::MissingAttribute("duration".to_string()));
+    view! { cx,
+        details(class="mb-5 text-sm") {
+            summary(class="font-bold") { "Podcast namespace adoption" }
+            (phases)
         }
     }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "soundbite".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
 }
 
-fn analyze_podcast_season(season: &badpod::podcast::Season) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &season.value {
-        Some(badpod::Integer::Ok(i)) => {
-            attributes.push((NODE_VALUE.to_string(), Value::Object(i.to_string())));
-        }
-        Some(badpod::Integer::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        None => {
-            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-        }
-    }
-
-    if let Some(name) = &season.name {
-        attributes.push(("name".to_string(), Value::Text(name.to_string())));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "season".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
+/// Describes how many of the items something applies to, e.g. “3 of 12 (25%)”.
+fn share_str(count: usize, items: usize) -> String {
+    match items {
+        0 => "none".to_string(),
+        _ => format!("{count} of {items} ({}%)", count * 100 / items),
     }
 }
 
-fn analyze_podcast_episode(episode: &badpod::podcast::Episode) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &episode.value {
-        Some(badpod::Number::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                NODE_VALUE.to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        Some(n) => {
-            attributes.push((NODE_VALUE.to_string(), Value::Object(n.to_string())));
-        }
-        None => {
-            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-        }
-    }
-
-    if let Some(display) = &episode.display {
-        attributes.push(("display".to_string(), Value::Text(display.to_string())));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "episode".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
+/// Describes the number of findings of each severity, e.g. “2 errors and 1 warning”.
+fn counts_str(report: &Report) -> String {
+    let counts = [Severity::Error, Severity::Warning, Severity::Info]
+        .into_iter()
+        .map(|severity| (severity, report.count(severity)))
+        .filter(|(_, n)| *n > 0)
+        .map(|(severity, n)| match (severity, n) {
+            (Severity::Error, 1) => "1 error".to_string(),
+            (Severity::Error, n) => format!("{n} errors"),
+            (Severity::Warning, 1) => "1 warning".to_string(),
+            (Severity::Warning, n) => format!("{n} warnings"),
+            (Severity::Info, 1) => "1 info message".to_string(),
+            (Severity::Info, n) => format!("{n} info messages"),
+        })
+        .collect::<Vec<_>>();
+    match counts.as_slice() {
+        [] => String::new(),
+        [x] => x.to_string(),
+        [init @ .., last] => format!("{} and {}", init.join(", "), last),
     }
 }
 
-fn analyze_podcast_alternate_enclosure(
-    alternate_enclosure: &badpod::podcast::AlternateEnclosure,
-) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-    let mut children = Vec::new();
-
-    if let Some(type_) = &alternate_enclosure.type_ {
-        match type_ {
-            badpod::MimeEnclosure::Other((s, _)) => {
-                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
-            }
-            _ => {
-                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("type".to_string()));
-    }
-
-    if let Some(length) = &alternate_enclosure.length {
-        match length {
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "length".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("length".to_string(), Value::Object(length.to_string())));
-            }
+/// Collects the rules with findings in the node and its descendants, with their default severity
+/// and the number of findings.
+fn collect_rules(node: &Node, rules: &mut Vec<(String, Severity, usize)>) {
+    for error in &node.errors {
+        let id = rule_id(node, error);
+        match rules.iter_mut().find(|(x, _, _)| *x == id) {
+            Some((_, _, count)) => *count += 1,
+            None => rules.push((id, error.severity(), 1)),
         }
     }
-
-    if let Some(bit_rate) = &alternate_enclosure.bit_rate {
-        match bit_rate {
-            badpod::Float::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "bitrate".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("bitrate".to_string(), Value::Object(bit_rate.to_string())));
-            }
-        }
+    for child in &node.children {
+        collect_rules(child, rules);
     }
+}
 
-    if let Some(height) = &alternate_enclosure.height {
-        match height {
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "height".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("height".to_string(), Value::Object(height.to_string())));
+#[component(inline_props)]
+fn DisplayRuleSettings<'a, G: Html>(
+    cx: Scope<'a>,
+    rules: &'a ReadSignal<Vec<(String, Severity, usize)>>,
+    settings: &'a Signal<RuleSettings>,
+) -> View<G> {
+    view! { cx,
+        details(class="mb-5 text-sm") {
+            summary(class="font-bold") { "Rule settings" }
+            p(class="text-gray-500") {
+                "Disable rules you have decided to ignore, or lower their severity. The settings are saved in this browser."
+            }
+            table(class="w-full") {
+                thead {
+                    tr(class="text-left") {
+                        th { "Rule" }
+                        th { "Findings" }
+                        th { "Report as" }
+                    }
+                }
+                tbody {
+                    Indexed(
+                        iterable=rules,
+                        view=move |cx, (id, severity, count)| {
+                            let level = create_signal(cx, match settings.get_untracked().level(&id) {
+                                None => "default",
+                                Some(RuleLevel::Off) => "off",
+                                Some(RuleLevel::Info) => "info",
+                                Some(RuleLevel::Warning) => "warning",
+                            }.to_string());
+                            let rule = id.clone();
+                            create_effect(cx, move || {
+                                let new_level = match level.get().as_str() {
+                                    "off" => Some(RuleLevel::Off),
+                                    "info" => Some(RuleLevel::Info),
+                                    "warning" => Some(RuleLevel::Warning),
+                                    _ => None,
+                                };
+                                if settings.get_untracked().level(&rule) != new_level {
+                                    settings.modify().set(&rule, new_level);
+                                }
+                            });
+                            let default_str = format!("{severity} (default)");
+                            view! { cx,
+                                tr {
+                                    td { code(class="break-all") { (id) } }
+                                    td { (count) }
+                                    td {
+                                        select(
+                                            class="input-text-base rounded-xl w-auto py-1 text-sm",
+                                            bind:value=level,
+                                        ) {
+                                            option(value="default") { (default_str) }
+                                            option(value="warning") { "warning" }
+                                            option(value="info") { "info" }
+                                            option(value="off") { "off" }
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                    )
+                }
             }
         }
     }
+}
 
-    if let Some(lang) = &alternate_enclosure.language {
-        attributes.push(("lang".to_string(), Value::Text(lang.to_string())));
-    }
-
-    if let Some(title) = &alternate_enclosure.title {
-        attributes.push(("title".to_string(), Value::Text(title.to_string())));
-    }
+#[component(inline_props)]
+fn DisplayNode<'a, G: Html>(
+    cx: Scope<'a>,
+    node: Node,
+    is_root: bool,
+    min_severity: &'a ReadSignal<Severity>,
+    selected_span: &'a Signal<Option<Span>>,
+    source: &'a String,
+) -> View<G> {
+    let children = create_signal(cx, node.children.clone());
+    let all_errors = node
+        .errors
+        .iter()
+        .map(|error| {
+            let fix = match (fix::suggest(&node, error, source), node.span) {
+                (Some(fix), Some(span)) => {
+                    let element = fix.fixed_element(source, span);
+                    Some((fix.description, element))
+                }
+                _ => None,
+            };
+            (
+                error.clone(),
+                node.error_span(error),
+                rule_id(&node, error),
+                fix,
+            )
+        })
+        .collect::<Vec<_>>();
+    let errors = create_memo(cx, move || {
+        all_errors
+            .iter()
+            .filter(|(error, _, _, _)| error.severity() >= *min_severity.get())
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+    let attributes = create_signal(cx, node.attributes.clone());
+    let max_severity = node.max_severity();
+    let name_cls = move || match max_severity {
+        Some(severity) if severity >= *min_severity.get() => severity_text_cls(severity),
+        _ => "",
+    };
+    let details_cls = if is_root { "overflow-x-auto" } else { "" };
 
-    if let Some(rel) = &alternate_enclosure.rel {
-        if rel.len() > 32 {
-            errors.push(Error::AttributeExceedsMaxLength(
-                "rel".to_string(),
-                rel.to_string(),
-                32,
-            ));
-        } else {
-            attributes.push(("rel".to_string(), Value::Text(rel.to_string())));
-        }
+    view! { cx,
+            details(class=details_cls, open=max_severity >= Some(Severity::Warning)) {
+            summary(class=name_cls()) {
+                code(class="font-bold") { "<"(node.name)">" }
     }
+    div(class="pl-1") {
+                    div(class="pl-2 md:pl-4 border-l-2 border-gray-200") {
+                        ul(class="text-sm my-0") {
+                            Indexed(
+                                iterable=errors,
+                                view=move |cx, (error, span, rule_id, fix)| {
+                                    let marker_cls = severity_marker_cls(error.severity());
+                                    view! { cx,
+                                    li(class=format!("my-0 {marker_cls}")) {
+                                        (if let Some(span) = span {
+                                            view! { cx,
+                                                button(
+                                                    class="float-right ml-2 text-xs text-gray-400 hover:text-primary-500",
+                                                    type="button",
+                                                    title="Show in source",
+                                                    on:click=move |_| selected_span.set(Some(span)),
+                                                ) {
+                                                    (format!("line {}, col {}", span.line, span.column))
+                                                }
+                                            }
+                                        } else {
+                                            view! { cx, }
+                                        })
+                                        DisplayError(error=error, rule_id=Some(rule_id))
+                                        (if let Some((description, element)) = fix.clone() {
+                                            view! { cx,
+                                                div(class="mt-1") {
+                                                    span(class="font-bold") { "Suggested fix: " }
+                                                    (description)
+                                                    pre(class="mt-1 p-2 bg-success-50 rounded-lg text-xs whitespace-pre-wrap") { code { (element) } }
+                                                }
+                                            }
+                                        } else {
+                                            view! { cx, }
+                                        })
+                                    }
+                                    }
+                                },
+                            )
+                                Indexed(
+                                    iterable=attributes,
+                                    view=|cx, x| view! { cx,
+                                        li(class="my-0") {
+                                            code { span(class="attr") { (x.0) }  "=" }
+                                            (match &x.1 {
+                                                Value::Text(s) => {
+                                                    let s = s.to_string();
+                                                    view! { cx, "“" (s) "”" }
+                                                },
+                                                Value::Object(s) => {
+                                                    let s = s.to_string();
+                                                    view! { cx, span(class="italic") { (s) } }
+                                                },
+                                                Value::Url(s) => {
+                                                    let s = s.to_string();
+                                                    if s.starts_with("http") {
+                                                        view! { cx, utils::Link(url=s.to_string(), text=s.to_string(), new_tab=true) }
+                                                    } else {
+                                                        view! { cx, span(class="italic") { (s) } }
+                                                    }
+                                                }
+                                            }
+                                            )
+                                        }
+                                    },
+                                )
+                        }
 
-    if let Some(default) = &alternate_enclosure.default {
-        match default {
-            badpod::Bool::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "default".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push(("default".to_string(), Value::Object(default.to_string())));
+                        Indexed(
+                            iterable=children,
+                            view=move |cx, x| view! { cx,
+                                DisplayNode(node=x, is_root=false, min_severity=min_severity, selected_span=selected_span, source=source)
+                            },
+                        )
+                    }
+                }
             }
         }
-    }
-
-    for source in &alternate_enclosure.podcast_source {
-        children.push(analyze_podcast_source(source));
-    }
-    if children.is_empty() {
-        errors.push(Error::MissingChild(TagName(
-            Some(Namespace::Podcast),
-            "source".to_string(),
-        )));
-    }
-
-    for integrity in &alternate_enclosure.podcast_integrity {
-        children.push(analyze_podcast_integrity(integrity));
-    }
-    if alternate_enclosure.podcast_integrity.len() > 1 {
-        errors.push(Error::MultipleChildren(TagName(
-            Some(Namespace::Podcast),
-            "integrity".to_string(),
-        )));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "alternateEnclosure".to_string()),
-        errors,
-        attributes,
-        children,
-    }
 }
 
-fn analyze_podcast_source(source: &badpod::podcast::Source) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
+#[component(inline_props)]
+fn DisplaySource<'a, G: Html>(
+    cx: Scope<'a>,
+    source: String,
+    selected_span: &'a ReadSignal<Option<Span>>,
+) -> View<G> {
+    let lines = create_ref(cx, highlight_xml(&source));
+    let html = create_memo(cx, move || {
+        let selected = *selected_span.get();
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let n = i + 1;
+                let cls = match selected {
+                    Some(span) if span.line <= n && n <= span.end_line => "bg-warning-100",
+                    _ => "",
+                };
+                format!("<div id=\"source-line-{n}\" class=\"{cls}\"><span class=\"inline-block w-12 pr-3 text-right text-gray-400 select-none\">{n}</span>{line}</div>")
+            })
+            .collect::<String>()
+    });
 
-    if let Some(uri) = &source.uri {
-        match uri {
-            badpod::Url::Ok(url) => {
-                attributes.push(("uri".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "uri".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
+    create_effect(cx, move || {
+        if let Some(span) = *selected_span.get() {
+            if let Some(window) = web_sys::window() {
+                if let Some(document) = window.document() {
+                    if let Some(line) =
+                        document.get_element_by_id(&format!("source-line-{}", span.line))
+                    {
+                        line.scroll_into_view();
+                    }
+                }
             }
         }
-    } else {
-        errors.push(Error::MissingAttribute("uri".to_string()));
-    }
+    });
 
-    if let Some(content_type) = &source.type_ {
-        match content_type {
-            badpod::MimeEnclosure::Other((s, _)) => {
-                errors.push(Error::InvalidAttribute(
-                    "contentType".to_string(),
-                    s.to_string(),
-                ));
-            }
-            _ => {
-                attributes.push((
-                    "contentType".to_string(),
-                    Value::Object(content_type.to_string()),
-                ));
-            }
+    view! { cx,
+        details(class="mt-5", open=selected_span.get().is_some()) {
+            summary(class="font-bold") { "Source" }
+            pre(
+                class="p-1 max-h-96 overflow-auto text-xs",
+                dangerously_set_inner_html=html.get().as_str(),
+            ){}
         }
     }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "source".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
 }
 
-fn analyze_podcast_integrity(integrity: &badpod::podcast::Integrity) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    match &integrity.type_ {
-        Some(badpod::podcast::IntegrityType::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "type".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        Some(t) => {
-            attributes.push(("type".to_string(), Value::Object(t.to_string())));
+/// Splits the source into lines of HTML with syntax highlighting.
+fn highlight_xml(source: &str) -> Vec<String> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Text,
+        Markup,
+        Value(char),
+        Comment,
+        CData,
+    }
+
+    let cls = |state: State| match state {
+        State::Text => None,
+        State::Markup => Some("text-primary-600"),
+        State::Value(_) => Some("text-success-600"),
+        State::Comment | State::CData => Some("text-gray-400"),
+    };
+    let open = |line: &mut String, state: State| {
+        if let Some(cls) = cls(state) {
+            line.push_str(&format!("<span class=\"{cls}\">"));
         }
-        None => {
-            errors.push(Error::MissingAttribute("type".to_string()));
+    };
+    let close = |line: &mut String, state: State| {
+        if cls(state).is_some() {
+            line.push_str("</span>");
         }
-    }
-
-    if let Some(value) = &integrity.value {
-        attributes.push(("value".to_string(), Value::Text(value.to_string())));
-    } else {
-        errors.push(Error::MissingAttribute("value".to_string()));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "integrity".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
-    }
-}
-
-fn analyze_podcast_social_interact(social_interact: &badpod::podcast::SocialInteract) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
+    };
 
-    if let Some(uri) = &social_interact.uri {
-        match uri {
-            badpod::Url::Ok(url) => {
-                attributes.push(("uri".to_string(), Value::Url(url.to_string())));
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut state = State::Text;
+
+    for (i, c) in source.char_indices() {
+        let new_state = match state {
+            State::Text if c == '<' => {
+                if source[i..].starts_with("<!--") {
+                    State::Comment
+                } else if source[i..].starts_with("<![CDATA[") {
+                    State::CData
+                } else {
+                    State::Markup
+                }
             }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "uri".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
+            State::Markup if c == '"' || c == '\'' => State::Value(c),
+            _ => state,
+        };
+        let entered = new_state != state;
+        if entered {
+            close(&mut line, state);
+            state = new_state;
+            open(&mut line, state);
+        }
+
+        match c {
+            '\n' => {
+                close(&mut line, state);
+                lines.push(std::mem::take(&mut line));
+                open(&mut line, state);
+            }
+            '<' => line.push_str("&lt;"),
+            '>' => line.push_str("&gt;"),
+            '&' => line.push_str("&amp;"),
+            '"' => line.push_str("&quot;"),
+            _ => line.push(c),
+        }
+
+        let new_state = match state {
+            State::Markup if c == '>' => State::Text,
+            State::Value(quote) if c == quote && !entered => State::Markup,
+            State::Comment if c == '>' && source[..=i].ends_with("-->") => State::Text,
+            State::CData if c == '>' && source[..=i].ends_with("]]>") => State::Text,
+            _ => state,
+        };
+        if new_state != state {
+            let was_value = matches!(state, State::Value(_));
+            close(&mut line, state);
+            state = new_state;
+            if was_value {
+                open(&mut line, state);
             }
         }
-    } else {
-        errors.push(Error::MissingAttribute("uri".to_string()));
     }
+    close(&mut line, state);
+    lines.push(line);
 
-    match &social_interact.protocol {
-        Some(badpod::podcast::SocialProtocol::Other((s, reason))) => {
-            errors.push(Error::InvalidAttributeWithReason(
-                "protocol".to_string(),
-                s.to_string(),
-                reason.to_string(),
-            ));
-        }
-        Some(p) => {
-            attributes.push(("protocol".to_string(), Value::Object(p.to_string())));
-        }
-        None => {
-            errors.push(Error::MissingAttribute("protocol".to_string()));
-        }
-    }
+    lines
+}
 
-    if let Some(account_id) = &social_interact.account_id {
-        attributes.push(("accountId".to_string(), Value::Text(account_id.to_string())));
-    }
+#[component(inline_props)]
+fn DownloadReport<'a, G: Html>(
+    cx: Scope<'a>,
+    report: Report,
+    feed_url: Option<String>,
+    source: &'a String,
+) -> View<G> {
+    let (fixed_source, fix_count) = fix::apply_all(&report.root, source);
+    let fixed_source = create_ref(cx, fixed_source);
+    let report = create_ref(cx, report);
+    let feed_url = create_ref(cx, feed_url);
+    let download_error = create_signal(cx, None::<String>);
+
+    let download_json = move |_| {
+        let result = serde_json::to_string_pretty(report)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                utils::download_file("validation-report.json", "application/json", &json)
+            });
+        download_error.set(result.err());
+    };
 
-    if let Some(account_url) = &social_interact.account_url {
-        match account_url {
-            badpod::Url::Ok(url) => {
-                attributes.push(("accountUrl".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "accountUrl".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    }
+    let download_sarif = move |_| {
+        let sarif = validator::sarif::to_sarif(report, feed_url.as_deref());
+        let result = utils::download_file(
+            "validation-report.sarif",
+            "application/sarif+json",
+            &sarif.to_string(),
+        );
+        download_error.set(result.err());
+    };
+
+    let download_fixed = move |_| {
+        let result = utils::download_file("feed-fixed.xml", "application/xml", fixed_source);
+        download_error.set(result.err());
+    };
 
-    if let Some(priority) = &social_interact.priority {
-        match priority {
-            badpod::Integer::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "priority".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
+    view! { cx,
+        div(class="flex flex-row flex-wrap items-center gap-2 mt-5") {
+            span(class="text-sm text-gray-500") { "Download report:" }
+            button(class="btn-base btn-primary rounded-xl py-1 text-sm", type="button", on:click=download_json) { "JSON" }
+            button(class="btn-base btn-primary rounded-xl py-1 text-sm", type="button", on:click=download_sarif) { "SARIF" }
+        }
+        (if fix_count > 0 {
+            let label = match fix_count {
+                1 => "Download fixed feed (1 fix)".to_string(),
+                n => format!("Download fixed feed ({n} fixes)"),
+            };
+            view! { cx,
+                div(class="flex flex-row flex-wrap items-center gap-2 mt-2") {
+                    button(class="btn-base btn-primary rounded-xl py-1 text-sm", type="button", on:click=download_fixed) { (label) }
+                    span(class="text-sm text-gray-500") { "Applies the suggested fixes to the original XML." }
+                }
             }
-            _ => {
-                attributes.push(("priority".to_string(), Value::Object(priority.to_string())));
+        } else {
+            view! { cx, }
+        })
+        (if let Some(e) = download_error.get().as_ref() {
+            view! { cx,
+                div(class="mt-2") {
+                    utils::Alert(type_=utils::AlertType::Danger, msg=format!("Could not download the report ({e})"))
+                }
             }
-        }
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "socialInteract".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
+        } else {
+            view! { cx, }
+        })
     }
 }
 
@@ -2270,37 +2072,56 @@ fn DisplayProgramError<G: Html>(cx: Scope, program_error: ProgramError<G>) -> Vi
     }
 }
 
-fn analyze_podcast_content_link(content_link: &badpod::podcast::ContentLink) -> Node {
-    let mut errors = Vec::new();
-    let mut attributes = Vec::new();
-
-    if let Some(value) = &content_link.value {
-        attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
-    } else {
-        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
-    }
-
-    if let Some(href) = &content_link.href {
-        match href {
-            badpod::Url::Ok(url) => {
-                attributes.push(("href".to_string(), Value::Url(url.to_string())));
-            }
-            badpod::Url::Other((s, reason)) => {
-                errors.push(Error::InvalidAttributeWithReason(
-                    "href".to_string(),
-                    s.to_string(),
-                    reason.to_string(),
-                ));
-            }
-        }
-    } else {
-        errors.push(Error::MissingAttribute("href".to_string()));
-    }
-
-    Node {
-        name: TagName(Some(Namespace::Podcast), "contentLink".to_string()),
-        errors,
-        attributes,
-        ..Default::default()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use app::validator::rules::Rule;
+
+    fn rules(bytes: &[u8]) -> Vec<Rule> {
+        syntax::analyze(bytes)
+            .1
+            .into_iter()
+            .filter_map(|diagnostic| match diagnostic.error {
+                Error::Custom(rule, _) => Some(rule),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_source_bytes() {
+        let feed = "<rss version=\"2.0\"><channel><title>Café</title></channel></rss>";
+
+        let bom = SourceFile::new([b"\xEF\xBB\xBF", feed.as_bytes()].concat());
+        assert_eq!(bom.text, feed);
+        assert_eq!(
+            rules(&source_bytes(feed, Some(&bom))),
+            [Rule::ByteOrderMark]
+        );
+
+        // Each character of the feed fits in a byte in ISO-8859-1.
+        let latin1 = SourceFile::new(feed.chars().map(|c| c as u8).collect());
+        assert_eq!(latin1.text, feed.replace('é', "�"));
+        assert_eq!(
+            rules(&source_bytes(&latin1.text, Some(&latin1))),
+            [Rule::EncodingMismatch]
+        );
+
+        let utf16: Vec<u8> = std::iter::once(0xFEFF)
+            .chain(feed.encode_utf16())
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let utf16 = SourceFile::new(utf16);
+        assert_eq!(utf16.text, feed);
+        assert_eq!(
+            rules(&source_bytes(feed, Some(&utf16))),
+            [Rule::NonUtf8Encoding]
+        );
+
+        // Once the text is edited or pasted, it is validated as it is shown.
+        let edited = format!("{feed}\n");
+        assert_eq!(source_bytes(&edited, Some(&bom)), edited.as_bytes());
+        assert_eq!(source_bytes(feed, None), feed.as_bytes());
+        assert!(rules(&source_bytes(feed, None)).is_empty());
     }
 }
@@ -0,0 +1,102 @@
+use super::validator::{md_to_html, severity_text_cls};
+use crate::components::utils;
+use app::validator::rules::{rule_doc, Rule};
+use sycamore::prelude::*;
+
+#[component]
+pub fn ValidatorRules<G: Html>(cx: Scope) -> View<G> {
+    let rules = Rule::ALL
+        .into_iter()
+        .map(|rule| {
+            let href = format!("/validator/rules/{}", rule.id());
+            view! { cx,
+                li {
+                    a(class="link", href=href) { (rule.title()) }
+                    " "
+                    code(class="text-xs text-gray-500") { (rule.id()) }
+                }
+            }
+        })
+        .collect();
+    let rules = View::new_fragment(rules);
+
+    view! { cx,
+        crate::components::ToolsBreadcrumbs(title="Validator Rules")
+
+        h1(class="mb-3") { "Validator Rules" }
+        h2(class="mt-3 text-gray-500") { "What the " a(class="link", href="/validator") { "Podcast Validator" } " checks and why." }
+
+        p(class="mt-7") {
+            "Every finding of the validator has a rule ID, which can be used to disable or downgrade the rule in the rule settings of a report."
+        }
+
+        h3 { "Structure" }
+        p {
+            "Most findings concern the structure of an element. Their IDs consist of the element, the kind of the problem and the attribute or child it concerns, e.g. "
+            a(class="link", href="/validator/rules/podcast:transcript.missing-attribute.url") { code { "podcast:transcript.missing-attribute.url" } }
+            ". The kinds are:"
+        }
+        ul {
+            li { code { "missing-attribute" } ": a required attribute or value is missing." }
+            li { code { "invalid-attribute" } ": an attribute or value does not have the required format." }
+            li { code { "max-length" } ": an attribute or value is too long." }
+            li { code { "missing-child" } ": a required child element is missing." }
+            li { code { "multiple-children" } ": a child element that may appear once appears more than once." }
+        }
+        p {
+            "Attribute " code { "value" } " stands for the text content of the element."
+        }
+
+        h3 { "Other checks" }
+        ul {
+            (rules)
+        }
+    }
+}
+
+#[component(inline_props)]
+pub fn ValidatorRule<G: Html>(cx: Scope, id: String) -> View<G> {
+    let doc = match rule_doc(&id) {
+        Some(doc) => doc,
+        None => {
+            return view! { cx,
+                crate::components::ToolsBreadcrumbs(title="Validator Rules")
+                utils::Alert(type_=utils::AlertType::Danger, msg=format!("Unknown rule “{id}”."))
+                p {
+                    a(class="link", href="/validator/rules") { "See all rules" }
+                }
+            };
+        }
+    };
+
+    let severity_cls = severity_text_cls(doc.severity);
+    let description = md_to_html(&doc.description);
+
+    view! { cx,
+        crate::components::ToolsBreadcrumbs(title="Validator Rules")
+
+        h1(class="mb-3") { (doc.title) }
+        p(class="mt-3 text-gray-500") {
+            code { (doc.id) }
+            " · "
+            span(class=severity_cls) { (doc.severity) }
+        }
+
+        p(class="mt-7") {
+            span(class="from-md", dangerously_set_inner_html=description.as_str())
+        }
+        p {
+            "Specification: "
+            utils::Link(url=doc.spec_url.clone(), text=doc.spec_url, new_tab=true)
+        }
+
+        h3 { "Bad" }
+        pre(class="p-2 bg-danger-50 rounded-lg text-xs") { code { (doc.bad_example) } }
+        h3 { "Good" }
+        pre(class="p-2 bg-success-50 rounded-lg text-xs") { code { (doc.good_example) } }
+
+        p(class="mt-7") {
+            a(class="link", href="/validator/rules") { "See all rules" }
+        }
+    }
+}
@@ -14,6 +14,10 @@ enum AppRoutes {
     PlotOp3,
     #[to("/validator")]
     Validator,
+    #[to("/validator/rules")]
+    ValidatorRules,
+    #[to("/validator/rules/<id>")]
+    ValidatorRule(String),
     #[not_found]
     NotFound,
 }
@@ -48,6 +52,11 @@ fn switch<'a, G: Html>(cx: Scope<'a>, route: &'a ReadSignal<AppRoutes>) -> View<
                 AppRoutes::PodcastGuid => view!{ cx, components::PodcastGuid{}},
                 AppRoutes::PlotOp3 => view!{ cx, components::PlotOp3{}},
                 AppRoutes::Validator => view!{ cx, components::Validator{}},
+                AppRoutes::ValidatorRules => view!{ cx, components::ValidatorRules{}},
+                AppRoutes::ValidatorRule(id) => {
+                    let id = id.clone();
+                    view!{ cx, components::ValidatorRule(id=id)}
+                },
                 AppRoutes::NotFound => view! { cx,
                     "404 Not Found"
                 },
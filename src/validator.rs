@@ -0,0 +1,2916 @@
+//! Podcast feed validation that does not depend on the browser.
+//!
+//! The web tool renders the [`Node`] tree returned from here, and the same rules can be reused
+//! natively, for example:
+//!
+//! ```no_run
+//! let report = app::validator::validate_str("<rss></rss>").unwrap();
+//! if report.has_errors() {
+//!     // ...
+//! }
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub mod adoption;
+mod consistency;
+pub mod diff;
+pub mod fix;
+mod itunes;
+mod license;
+mod namespaces;
+mod person;
+pub mod podcast_guid;
+pub mod redirects;
+pub mod rules;
+pub mod sarif;
+mod show_notes;
+pub mod source;
+pub mod summary;
+pub mod syntax;
+pub mod transport;
+mod v4v;
+
+use rules::Rule;
+use source::Span;
+
+/// Analysis of a whole feed.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Report {
+    pub root: Node,
+}
+
+impl Report {
+    pub fn has_errors(&self) -> bool {
+        self.root.descendants_have_errors()
+    }
+
+    /// Number of findings of the given severity in the whole feed.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.root.count(severity)
+    }
+
+    pub fn has_podcast_tags(&self) -> bool {
+        self.root.descendants_have_podcast_tags()
+    }
+}
+
+/// Parses feed XML and analyzes it.
+///
+/// Unlike [`validate`], this also records where in the source each node comes from.
+pub fn validate_str(xml: &str) -> Result<Report, String> {
+    validate_source(xml, None)
+}
+
+/// Like [`validate_str`], but also checks the feed against the URL it was fetched from.
+pub fn validate_fetched(xml: &str, feed_url: &str) -> Result<Report, String> {
+    validate_source(xml, Some(feed_url))
+}
+
+fn validate_source(xml: &str, feed_url: Option<&str>) -> Result<Report, String> {
+    let feed = badpod::from_str(xml).map_err(|e| e.to_string())?;
+    let mut report = validate(&feed);
+    if let Some(element) = source::parse(xml) {
+        source::attach_spans(&mut report.root, &element);
+        namespaces::analyze(&mut report.root, &element);
+        show_notes::analyze(&mut report.root, &element, xml);
+        analyze_rfc2822_dates(&mut report.root, xml);
+        podcast_guid::analyze(&mut report.root, &element, xml, feed_url);
+    }
+    Ok(report)
+}
+
+/// Analyzes an already parsed feed.
+pub fn validate(feed: &badpod::Rss) -> Report {
+    Report {
+        root: analyze_rss(feed),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Text(String),
+    Object(String),
+    Url(String),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub enum Namespace {
+    Podcast,
+    Itunes,
+}
+
+/// Serialized as the qualified name, e.g. `podcast:guid`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct TagName(pub Option<Namespace>, pub String);
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Node {
+    pub name: TagName,
+    pub children: Vec<Node>,
+    pub attributes: Vec<(String, Value)>,
+    pub errors: Vec<Error>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    /// Positions of the attributes, with [`NODE_VALUE`] standing for the content.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attribute_spans: Vec<(String, Span)>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub enum Error {
+    MissingAttribute(String),
+    InvalidAttribute(String, String),
+    InvalidAttributeWithReason(String, String, String),
+    MissingChild(TagName),
+    MultipleChildren(TagName),
+    AttributeExceedsMaxLength(String, String, usize),
+    Custom(Rule, String),
+    CustomWithExtraInfo(Rule, String, String),
+    /// Finding whose severity the user has lowered, see [`rules::RuleSettings`].
+    Downgraded(Severity, Box<Error>),
+}
+
+/// How serious a finding is, in increasing order.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Severity {
+    /// Something worth knowing that does not need to be fixed.
+    Info,
+    /// Likely to cause problems in some apps, or deprecated.
+    Warning,
+    /// Violates the specification.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!("unknown severity \"{s}\"")),
+        }
+    }
+}
+
+impl Error {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Error::Custom(rule, _) | Error::CustomWithExtraInfo(rule, _, _) => rule.severity(),
+            Error::Downgraded(severity, _) => *severity,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Attribute name used for the text content of an element.
+pub const NODE_VALUE: &str = "node value";
+
+impl std::fmt::Display for TagName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagName(Some(Namespace::Podcast), x) => write!(f, "podcast:{}", x),
+            TagName(Some(Namespace::Itunes), x) => write!(f, "itunes:{}", x),
+            TagName(None, x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl Serialize for TagName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TagName {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        match s.split_once(':') {
+            Some(("podcast", name)) => Ok(TagName(Some(Namespace::Podcast), name.to_string())),
+            Some(("itunes", name)) => Ok(TagName(Some(Namespace::Itunes), name.to_string())),
+            _ => Ok(TagName(None, s)),
+        }
+    }
+}
+
+/// Plain-text description, as opposed to the HTML rendered by the web tool.
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingAttribute(attr) if attr == NODE_VALUE => write!(f, "Missing node value"),
+            Error::MissingAttribute(attr) => write!(f, "Missing attribute `{}`", attr),
+            Error::InvalidAttribute(attr, value) if attr == NODE_VALUE => {
+                write!(f, "Invalid node value “{}”", value)
+            }
+            Error::InvalidAttribute(attr, value) => {
+                write!(f, "Attribute `{}` has invalid value “{}”", attr, value)
+            }
+            Error::InvalidAttributeWithReason(attr, value, reason) if attr == NODE_VALUE => {
+                write!(f, "Invalid node value “{}”: {}", value, reason)
+            }
+            Error::InvalidAttributeWithReason(attr, value, reason) => write!(
+                f,
+                "Attribute `{}` has invalid value “{}”: {}",
+                attr, value, reason
+            ),
+            Error::MissingChild(tag_name) => write!(f, "Missing child <{}>", tag_name),
+            Error::MultipleChildren(tag_name) => {
+                write!(f, "Only one child <{}> is allowed", tag_name)
+            }
+            Error::AttributeExceedsMaxLength(attr, _, max_len) if attr == NODE_VALUE => write!(
+                f,
+                "Node value exceeds maximum length of {} characters",
+                max_len
+            ),
+            Error::AttributeExceedsMaxLength(attr, _, max_len) => write!(
+                f,
+                "Attribute `{}` exceeds maximum length of {} characters",
+                attr, max_len
+            ),
+            Error::Custom(_, msg) | Error::CustomWithExtraInfo(_, msg, _) => {
+                write!(f, "{}", strip_html(msg))
+            }
+            Error::Downgraded(_, error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Removes tags from the HTML snippets used in custom messages and decodes their entities.
+fn strip_html(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&text)
+}
+
+/// Escapes text that comes from the feed or its server, so that it can be put in the HTML of custom
+/// messages, which the web tool renders as is.
+fn escape_html(text: &str) -> String {
+    let mut html = String::new();
+    for c in text.chars() {
+        match c {
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '&' => html.push_str("&amp;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            _ => html.push(c),
+        }
+    }
+    html
+}
+
+/// Decodes the entities that custom messages use, in a single pass so that e.g. `&amp;lt;` becomes
+/// `&lt;` rather than `<`.
+fn decode_entities(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('&') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                name => {
+                    let number = name.strip_prefix('#')?;
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => number.parse().ok(),
+                    };
+                    code.and_then(char::from_u32)
+                }
+            }?;
+            Some((c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                text.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+impl Node {
+    pub fn descendants_have_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Position of the source that the error refers to, if known.
+    pub fn error_span(&self, error: &Error) -> Option<Span> {
+        if let Error::Downgraded(_, error) = error {
+            return self.error_span(error);
+        }
+        let attr = match error {
+            Error::InvalidAttribute(attr, _)
+            | Error::InvalidAttributeWithReason(attr, _, _)
+            | Error::AttributeExceedsMaxLength(attr, _, _) => Some(attr),
+            _ => None,
+        };
+
+        attr.and_then(|attr| {
+            self.attribute_spans
+                .iter()
+                .find(|(name, _)| name == attr)
+                .map(|(_, span)| *span)
+        })
+        .or(self.span)
+    }
+
+    /// Highest severity of the findings in this node and its descendants.
+    pub fn max_severity(&self) -> Option<Severity> {
+        let own = self.errors.iter().map(|error| error.severity()).max();
+        let nested = self
+            .children
+            .iter()
+            .filter_map(|child| child.max_severity())
+            .max();
+        own.max(nested)
+    }
+
+    /// Number of findings of the given severity in this node and its descendants.
+    pub fn count(&self, severity: Severity) -> usize {
+        let own = self
+            .errors
+            .iter()
+            .filter(|error| error.severity() == severity)
+            .count();
+        own + self
+            .children
+            .iter()
+            .map(|child| child.count(severity))
+            .sum::<usize>()
+    }
+
+    pub fn descendants_have_podcast_tags(&self) -> bool {
+        if self.name.0 == Some(Namespace::Podcast) {
+            return true;
+        }
+
+        for child in &self.children {
+            if child.descendants_have_podcast_tags() {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+pub fn analyze_rss(rss: &badpod::Rss) -> Node {
+    let mut errors = Vec::new();
+    let mut children = Vec::new();
+
+    for channel in &rss.channel {
+        let mut node = analyze_channel(channel);
+        consistency::analyze_items(channel, &mut node);
+        consistency::analyze_live_items(channel, &mut node);
+        children.push(node);
+    }
+    match rss.channel.len() {
+        0 => errors.push(Error::MissingChild(TagName(None, "channel".to_string()))),
+        1 => (),
+        _ => errors.push(Error::MultipleChildren(TagName(
+            None,
+            "channel".to_string(),
+        ))),
+    }
+
+    Node {
+        name: TagName(None, "rss".to_string()),
+        children,
+        errors,
+        ..Default::default()
+    }
+}
+
+fn analyze_channel(channel: &badpod::Channel) -> Node {
+    let mut errors = Vec::new();
+    let mut children = Vec::new();
+
+    for title in &channel.title {
+        children.push(analyze_title(title.to_string()));
+    }
+    match channel.title.len() {
+        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
+    }
+
+    for link in &channel.link {
+        children.push(analyze_link(link));
+    }
+    match channel.link.len() {
+        0 => errors.push(Error::MissingChild(TagName(None, "link".to_string()))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(None, "link".to_string()))),
+    }
+
+    for description in &channel.description {
+        children.push(analyze_description(description));
+    }
+    match channel.description.len() {
+        0 => errors.push(Error::MissingChild(TagName(
+            None,
+            "description".to_string(),
+        ))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            None,
+            "description".to_string(),
+        ))),
+    }
+
+    for language in &channel.language {
+        children.push(analyze_language(language));
+    }
+    if channel.language.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            None,
+            "language".to_string(),
+        )));
+    }
+
+    for pub_date in &channel.pub_date {
+        children.push(analyze_date("pubDate", pub_date));
+    }
+    if channel.pub_date.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            None,
+            "pubDate".to_string(),
+        )));
+    }
+
+    for last_build_date in &channel.last_build_date {
+        children.push(analyze_date("lastBuildDate", last_build_date));
+    }
+    if channel.last_build_date.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            None,
+            "lastBuildDate".to_string(),
+        )));
+    }
+
+    for image in &channel.itunes_image {
+        children.push(itunes::analyze_itunes_image(image));
+    }
+    match channel.itunes_image.len() {
+        0 => errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Itunes),
+            "image".to_string(),
+        ))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "image".to_string(),
+        ))),
+    }
+
+    for category in &channel.itunes_category {
+        children.push(itunes::analyze_itunes_category(category));
+    }
+    if channel.itunes_category.is_empty() {
+        errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Itunes),
+            "category".to_string(),
+        )));
+    }
+
+    for explicit in &channel.itunes_explicit {
+        children.push(itunes::analyze_itunes_explicit(explicit));
+    }
+    match channel.itunes_explicit.len() {
+        0 => errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Itunes),
+            "explicit".to_string(),
+        ))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "explicit".to_string(),
+        ))),
+    }
+
+    for owner in &channel.itunes_owner {
+        children.push(itunes::analyze_itunes_owner(owner));
+    }
+    if channel.itunes_owner.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "owner".to_string(),
+        )));
+    }
+
+    for type_ in &channel.itunes_type {
+        children.push(itunes::analyze_itunes_type(type_));
+    }
+    if channel.itunes_type.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "type".to_string(),
+        )));
+    }
+
+    for guid in &channel.podcast_guid {
+        children.push(analyze_podcast_guid(guid));
+    }
+    if channel.podcast_guid.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "guid".to_string(),
+        )));
+    }
+
+    for medium in &channel.podcast_medium {
+        children.push(analyze_podcast_medium(medium));
+    }
+    if channel.podcast_medium.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "medium".to_string(),
+        )));
+    }
+
+    for txt in &channel.podcast_txt {
+        children.push(analyze_podcast_txt(txt));
+    }
+
+    for block in &channel.podcast_block {
+        children.push(analyze_podcast_block(block));
+    }
+
+    for locked in &channel.podcast_locked {
+        children.push(analyze_podcast_locked(locked));
+    }
+    if channel.podcast_locked.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "locked".to_string(),
+        )));
+    }
+
+    for funding in &channel.podcast_funding {
+        children.push(analyze_podcast_funding(funding));
+    }
+
+    for location in &channel.podcast_location {
+        children.push(analyze_podcast_location(location));
+    }
+    if channel.podcast_location.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "location".to_string(),
+        )));
+    }
+
+    for person in &channel.podcast_person {
+        children.push(analyze_podcast_person(person));
+    }
+
+    for trailer in &channel.podcast_trailer {
+        children.push(analyze_podcast_trailer(trailer));
+    }
+
+    for license in &channel.podcast_license {
+        children.push(analyze_podcast_license(license));
+    }
+    if channel.podcast_license.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "license".to_string(),
+        )));
+    }
+
+    for v4v_value in &channel.podcast_value {
+        children.push(analyze_podcast_value(v4v_value));
+    }
+
+    for images in &channel.podcast_images {
+        children.push(analyze_podcast_images(images));
+    }
+    if channel.podcast_images.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "images".to_string(),
+        )));
+    }
+
+    for item in &channel.item {
+        children.push(analyze_item(item));
+    }
+
+    for live_item in &channel.podcast_live_item {
+        children.push(analyze_podcast_live_item(live_item));
+    }
+
+    Node {
+        name: TagName(None, "channel".to_string()),
+        children,
+        errors,
+        attributes: Vec::new(),
+        ..Default::default()
+    }
+}
+
+fn analyze_item(item: &badpod::Item) -> Node {
+    let mut children = Vec::new();
+    let mut errors = Vec::new();
+
+    for title in &item.title {
+        children.push(analyze_title(title.to_string()));
+    }
+    match item.title.len() {
+        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
+    }
+
+    for link in &item.link {
+        children.push(analyze_link(link));
+    }
+    if item.link.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(None, "link".to_string())));
+    }
+
+    for description in &item.description {
+        children.push(analyze_description(description));
+    }
+    if item.description.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            None,
+            "description".to_string(),
+        )));
+    }
+
+    for enclosure in &item.enclosure {
+        children.push(analyze_enclosure(enclosure));
+    }
+    match item.enclosure.len() {
+        0 => errors.push(Error::Custom(
+            Rule::ItemMissingEnclosure,
+            "Missing child <code>&lt;enclosure&gt;</code>. Most podcast apps ignore items without media.".to_string(),
+        )),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            None,
+            "enclosure".to_string(),
+        ))),
+    }
+
+    for guid in &item.guid {
+        children.push(analyze_guid(guid));
+    }
+    match item.guid.len() {
+        0 => errors.push(Error::Custom(
+            Rule::ItemMissingGuid,
+            "Missing child <code>&lt;guid&gt;</code>. Without it, apps may show the same episode more than once if its other details change.".to_string(),
+        )),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(None, "guid".to_string()))),
+    }
+
+    for pub_date in &item.pub_date {
+        children.push(analyze_date("pubDate", pub_date));
+    }
+    match item.pub_date.len() {
+        0 => errors.push(Error::Custom(
+            Rule::ItemMissingPubDate,
+            "Missing child <code>&lt;pubDate&gt;</code>. Apps use it to sort episodes.".to_string(),
+        )),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            None,
+            "pubDate".to_string(),
+        ))),
+    }
+
+    for image in &item.itunes_image {
+        children.push(itunes::analyze_itunes_image(image));
+    }
+    if item.itunes_image.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "image".to_string(),
+        )));
+    }
+
+    for explicit in &item.itunes_explicit {
+        children.push(itunes::analyze_itunes_explicit(explicit));
+    }
+    if item.itunes_explicit.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "explicit".to_string(),
+        )));
+    }
+
+    for duration in &item.itunes_duration {
+        children.push(itunes::analyze_itunes_duration(duration));
+    }
+    if item.itunes_duration.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "duration".to_string(),
+        )));
+    }
+
+    for episode_type in &item.itunes_type {
+        children.push(itunes::analyze_itunes_episode_type(episode_type));
+    }
+    if item.itunes_type.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "episodeType".to_string(),
+        )));
+    }
+
+    for v4v_value in &item.podcast_value {
+        children.push(analyze_podcast_value(v4v_value));
+    }
+
+    for person in &item.podcast_person {
+        children.push(analyze_podcast_person(person));
+    }
+
+    for location in &item.podcast_location {
+        children.push(analyze_podcast_location(location));
+    }
+    if item.podcast_location.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "location".to_string(),
+        )));
+    }
+
+    for images in &item.podcast_images {
+        children.push(analyze_podcast_images(images));
+    }
+    if item.podcast_images.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "images".to_string(),
+        )));
+    }
+
+    for txt in &item.podcast_txt {
+        children.push(analyze_podcast_txt(txt));
+    }
+
+    for transcript in &item.podcast_transcript {
+        children.push(analyze_podcast_transcript(transcript));
+    }
+
+    for chapters in &item.podcast_chapters {
+        children.push(analyze_podcast_chapters(chapters));
+    }
+    if item.podcast_chapters.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "chapters".to_string(),
+        )));
+    }
+
+    for soundbite in &item.podcast_soundbite {
+        children.push(analyze_podcast_soundbite(soundbite));
+    }
+
+    for season in &item.podcast_season {
+        children.push(analyze_podcast_season(season));
+    }
+    if item.podcast_season.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "season".to_string(),
+        )));
+    }
+
+    for episode in &item.podcast_episode {
+        children.push(analyze_podcast_episode(episode));
+    }
+    if item.podcast_episode.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "episode".to_string(),
+        )));
+    }
+
+    for license in &item.podcast_license {
+        children.push(analyze_podcast_license(license));
+    }
+    if item.podcast_license.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "license".to_string(),
+        )));
+    }
+
+    for alternate_enclosure in &item.podcast_alternate_enclosure {
+        children.push(analyze_podcast_alternate_enclosure(alternate_enclosure));
+    }
+
+    for social_interact in &item.podcast_social_interact {
+        children.push(analyze_podcast_social_interact(social_interact));
+    }
+
+    Node {
+        name: TagName(None, "item".to_string()),
+        children,
+        errors,
+        attributes: Vec::new(),
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_live_item(item: &badpod::podcast::LiveItem) -> Node {
+    let mut children = Vec::new();
+    let mut attributes = Vec::new();
+    let mut errors = Vec::new();
+
+    match &item.status {
+        Some(badpod::podcast::LiveItemStatus::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "status".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ))
+        }
+        Some(s) => attributes.push(("status".to_string(), Value::Object(s.to_string()))),
+        None => errors.push(Error::MissingAttribute("status".to_string())),
+    }
+
+    match &item.start {
+        Some(badpod::DateTime::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "start".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ))
+        }
+        Some(t) => attributes.push(("start".to_string(), Value::Object(t.to_string()))),
+        None => errors.push(Error::MissingAttribute("start".to_string())),
+    }
+
+    match &item.end {
+        Some(badpod::DateTime::Other((s, reason))) => errors.push(
+            Error::InvalidAttributeWithReason("end".to_string(), s.to_string(), reason.to_string()),
+        ),
+        Some(t) => attributes.push(("end".to_string(), Value::Object(t.to_string()))),
+        None => errors.push(Error::MissingAttribute("end".to_string())),
+    }
+
+    errors.extend(analyze_live_item_schedule(item, chrono::Utc::now()));
+
+    for title in &item.title {
+        children.push(analyze_title(title.to_string()));
+    }
+    match item.title.len() {
+        0 => errors.push(Error::MissingChild(TagName(None, "title".to_string()))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(None, "title".to_string()))),
+    }
+
+    for content_link in &item.podcast_content_link {
+        children.push(analyze_podcast_content_link(content_link));
+    }
+    if item.podcast_content_link.is_empty() {
+        errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Podcast),
+            "contentLink".to_string(),
+        )));
+    }
+
+    for v4v_value in &item.podcast_value {
+        children.push(analyze_podcast_value(v4v_value));
+    }
+
+    for person in &item.podcast_person {
+        children.push(analyze_podcast_person(person));
+    }
+
+    for location in &item.podcast_location {
+        children.push(analyze_podcast_location(location));
+    }
+    if item.podcast_location.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "location".to_string(),
+        )));
+    }
+
+    for images in &item.podcast_images {
+        children.push(analyze_podcast_images(images));
+    }
+    if item.podcast_images.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "images".to_string(),
+        )));
+    }
+
+    for txt in &item.podcast_txt {
+        children.push(analyze_podcast_txt(txt));
+    }
+
+    for transcript in &item.podcast_transcript {
+        children.push(analyze_podcast_transcript(transcript));
+    }
+
+    for chapters in &item.podcast_chapters {
+        children.push(analyze_podcast_chapters(chapters));
+    }
+    if item.podcast_chapters.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "chapters".to_string(),
+        )));
+    }
+
+    for soundbite in &item.podcast_soundbite {
+        children.push(analyze_podcast_soundbite(soundbite));
+    }
+
+    for season in &item.podcast_season {
+        children.push(analyze_podcast_season(season));
+    }
+    if item.podcast_season.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "season".to_string(),
+        )));
+    }
+
+    for episode in &item.podcast_episode {
+        children.push(analyze_podcast_episode(episode));
+    }
+    if item.podcast_episode.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "episode".to_string(),
+        )));
+    }
+
+    for license in &item.podcast_license {
+        children.push(analyze_podcast_license(license));
+    }
+    if item.podcast_license.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "license".to_string(),
+        )));
+    }
+
+    for alternate_enclosure in &item.podcast_alternate_enclosure {
+        children.push(analyze_podcast_alternate_enclosure(alternate_enclosure));
+    }
+
+    for social_interact in &item.podcast_social_interact {
+        children.push(analyze_podcast_social_interact(social_interact));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "liveItem".to_string()),
+        children,
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_title(title: String) -> Node {
+    Node {
+        name: TagName(None, "title".to_string()),
+        attributes: vec![(NODE_VALUE.to_string(), Value::Text(title.to_string()))],
+        ..Default::default()
+    }
+}
+
+fn analyze_link(link: &badpod::Url) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match link {
+        badpod::Url::Ok(url) => {
+            if url.scheme() != "http" && url.scheme() != "https" {
+                errors.push(Error::InvalidAttributeWithReason(
+                    NODE_VALUE.to_string(),
+                    url.to_string(),
+                    "Links should use `http` or `https` protocol.".to_string(),
+                ));
+            }
+            attributes.push((NODE_VALUE.to_string(), Value::Url(url.to_string())));
+        }
+        badpod::Url::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(None, "link".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_description(description: &str) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if description.trim().is_empty() {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    } else {
+        attributes.push((NODE_VALUE.to_string(), Value::Text(description.to_string())));
+    }
+
+    Node {
+        name: TagName(None, "description".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_language(language: &badpod::Language) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match language {
+        badpod::Language::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        _ => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(language.to_string())));
+        }
+    }
+
+    Node {
+        name: TagName(None, "language".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+/// Relates the status of a live item to its start and end times.
+fn analyze_live_item_schedule(
+    item: &badpod::podcast::LiveItem,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<Error> {
+    use badpod::podcast::LiveItemStatus;
+
+    let mut errors = Vec::new();
+    let tolerance = chrono::Duration::hours(1);
+
+    let start = match &item.start {
+        Some(badpod::DateTime::Ok(dt)) => Some(*dt),
+        _ => None,
+    };
+    let end = match &item.end {
+        Some(badpod::DateTime::Ok(dt)) => Some(*dt),
+        _ => None,
+    };
+
+    if let (Some(start), Some(end)) = (start, end) {
+        if end <= start {
+            errors.push(Error::Custom(
+                Rule::LiveItemEndBeforeStart,
+                "The end time is not after the start time.".to_string(),
+            ));
+        }
+    }
+
+    match &item.status {
+        Some(LiveItemStatus::Live) => {
+            if let Some(start) = start.filter(|start| now + tolerance < *start) {
+                errors.push(Error::Custom(
+                    Rule::LiveItemLiveBeforeStart,
+                    format!("Status is “live”, but the show only starts at {start}. Use “pending” until it starts."),
+                ));
+            }
+            if let Some(end) = end.filter(|end| *end + tolerance < now) {
+                errors.push(Error::Custom(
+                    Rule::LiveItemLiveAfterEnd,
+                    format!("Status is “live”, but the show ended at {end}. Use “ended” once it is over."),
+                ));
+            }
+        }
+        Some(LiveItemStatus::Pending) => {
+            if let Some(start) = start.filter(|start| *start + chrono::Duration::days(1) < now) {
+                errors.push(Error::Custom(
+                    Rule::LiveItemStalePending,
+                    format!("Status is “pending”, but the show was scheduled to start at {start}. Update the status or remove the item if the show was cancelled."),
+                ));
+            }
+        }
+        Some(LiveItemStatus::Ended)
+            if item.enclosure.is_empty() && item.podcast_alternate_enclosure.is_empty() =>
+        {
+            errors.push(Error::Custom(
+                Rule::LiveItemEndedWithoutReplay,
+                "Status is “ended”, but there is no enclosure with a replay of the show."
+                    .to_string(),
+            ));
+        }
+        _ => {}
+    }
+
+    errors
+}
+
+/// Analyzes `pubDate` and `lastBuildDate`.
+///
+/// badpod also accepts ISO 8601 dates, so the RFC 2822 format is checked in
+/// [`analyze_rfc2822_dates`] once the source is available.
+fn analyze_date(tag_name: &str, date: &badpod::DateTime) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match date {
+        badpod::DateTime::Ok(dt) => {
+            if *dt > chrono::Utc::now() + chrono::Duration::days(1) {
+                errors.push(Error::Custom(
+                    Rule::FutureDate,
+                    "Date is in the future. Some apps hide items until their publication date."
+                        .to_string(),
+                ));
+            }
+            attributes.push((NODE_VALUE.to_string(), Value::Object(dt.to_string())));
+        }
+        badpod::DateTime::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(None, tag_name.to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_enclosure(enclosure: &badpod::Enclosure) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &enclosure.url {
+        Some(badpod::Url::Ok(url)) => {
+            if url.scheme() != "http" && url.scheme() != "https" {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    url.to_string(),
+                    "Enclosures must be served over `http` or `https`.".to_string(),
+                ));
+            }
+            attributes.push(("url".to_string(), Value::Url(url.to_string())));
+        }
+        Some(badpod::Url::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "url".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => errors.push(Error::MissingAttribute("url".to_string())),
+    }
+
+    match &enclosure.length {
+        Some(badpod::Integer::Ok(i)) => {
+            if *i < 0 {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "length".to_string(),
+                    i.to_string(),
+                    "Length is the size of the file in bytes, so it cannot be negative."
+                        .to_string(),
+                ));
+            } else if *i == 0 {
+                errors.push(Error::Custom(
+                    Rule::EnclosureZeroLength,
+                    "Attribute <code class=\"font-bold\">length</code> is 0. This is allowed when the size is unknown, but some apps use it to show the download size.".to_string(),
+                ));
+            }
+            attributes.push(("length".to_string(), Value::Object(i.to_string())));
+        }
+        Some(badpod::Integer::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "length".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => errors.push(Error::MissingAttribute("length".to_string())),
+    }
+
+    match &enclosure.type_ {
+        Some(badpod::MimeEnclosure::Other((s, _))) => {
+            errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
+        }
+        Some(type_) => {
+            attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+        }
+        None => errors.push(Error::MissingAttribute("type".to_string())),
+    }
+
+    Node {
+        name: TagName(None, "enclosure".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_guid(guid: &badpod::Guid) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &guid.is_permalink {
+        Some(badpod::Bool::Ok(b)) => {
+            attributes.push(("isPermaLink".to_string(), Value::Object(b.to_string())));
+        }
+        Some(badpod::Bool::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "isPermaLink".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {}
+    }
+
+    // badpod already requires a URL unless `isPermaLink="false"` is set.
+    match &guid.value {
+        Some(badpod::GuidValue::Url(url)) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Url(url.to_string())));
+        }
+        Some(badpod::GuidValue::Text(s)) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(s.to_string())));
+        }
+        Some(badpod::GuidValue::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => errors.push(Error::MissingAttribute(NODE_VALUE.to_string())),
+    }
+
+    Node {
+        name: TagName(None, "guid".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+/// Text of a GUID, whether or not it is a URL.
+fn guid_str(value: &badpod::GuidValue) -> &str {
+    match value {
+        badpod::GuidValue::Url(url) => url.as_str(),
+        badpod::GuidValue::Text(s) | badpod::GuidValue::Other((s, _)) => s,
+    }
+}
+
+/// Checks that RSS dates follow RFC 2822, which badpod does not enforce.
+fn analyze_rfc2822_dates(node: &mut Node, source: &str) {
+    let is_date =
+        node.name.0.is_none() && ["pubDate", "lastBuildDate"].contains(&node.name.1.as_str());
+    let content = node
+        .attribute_spans
+        .iter()
+        .find(|(name, _)| name == NODE_VALUE)
+        .map(|(_, span)| source[span.start..span.end].trim());
+
+    let is_valid = !node
+        .errors
+        .iter()
+        .any(|error| error.severity() == Severity::Error);
+
+    if let (true, true, Some(content)) = (is_date, is_valid, content) {
+        if chrono::DateTime::parse_from_rfc2822(content).is_err() {
+            node.errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                content.to_string(),
+                "RSS dates must follow [RFC 2822](https://www.rfc-editor.org/rfc/rfc2822#section-3.3), e.g. “Sat, 07 Sep 2002 09:42:31 GMT”.".to_string(),
+            ));
+        }
+    }
+
+    for child in &mut node.children {
+        analyze_rfc2822_dates(child, source);
+    }
+}
+
+fn analyze_podcast_value(v4v_value: &badpod::podcast::Value) -> Node {
+    let mut children = Vec::new();
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    for recipient in &v4v_value.value_recipient {
+        children.push(analyze_podcast_value_recipient(recipient));
+    }
+    if v4v_value.value_recipient.is_empty() {
+        errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Podcast),
+            "valueRecipient".to_string(),
+        )));
+    }
+
+    match &v4v_value.type_ {
+        Some(badpod::podcast::ValueType::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "type".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ))
+        }
+        Some(type_) => {
+            attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("type".to_string()));
+        }
+    }
+
+    match &v4v_value.method {
+        Some(badpod::podcast::ValueMethod::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "method".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ))
+        }
+        Some(method) => {
+            attributes.push(("method".to_string(), Value::Object(method.to_string())));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("method".to_string()));
+        }
+    }
+
+    match &v4v_value.suggested {
+        Some(badpod::Float::Ok(f)) => {
+            attributes.push(("suggested".to_string(), Value::Object(f.to_string())));
+        }
+        Some(badpod::Float::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "suggested".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {}
+    };
+
+    let mut node = Node {
+        name: TagName(Some(Namespace::Podcast), "value".to_string()),
+        children,
+        errors,
+        attributes,
+        ..Default::default()
+    };
+    v4v::analyze_splits(v4v_value, &mut node);
+    node
+}
+
+fn analyze_podcast_value_recipient(recipient: &badpod::podcast::ValueRecipient) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(type_) = &recipient.type_ {
+        match type_ {
+            badpod::podcast::ValueRecipientType::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "type".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+            }
+        }
+    }
+
+    if let Some(address) = &recipient.address {
+        if let Some(type_) = &recipient.type_ {
+            errors.extend(v4v::analyze_address(&type_.to_string(), address));
+        }
+        attributes.push(("address".to_string(), Value::Text(address.to_string())));
+    } else {
+        errors.push(Error::MissingAttribute("address".to_string()));
+    }
+
+    if let Some(split) = &recipient.split {
+        match split {
+            badpod::Integer::Ok(i) => {
+                if *i < 0 {
+                    errors.push(Error::InvalidAttributeWithReason(
+                        "split".to_string(),
+                        i.to_string(),
+                        "Splits must not be negative.".to_string(),
+                    ));
+                }
+                attributes.push(("split".to_string(), Value::Object(i.to_string())));
+            }
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "split".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("split".to_string()));
+    }
+
+    if let Some(name) = &recipient.name {
+        attributes.push(("name".to_string(), Value::Text(name.to_string())));
+    }
+
+    if let Some(custom_key) = &recipient.custom_key {
+        attributes.push(("customKey".to_string(), Value::Text(custom_key.to_string())));
+    }
+
+    if let Some(custom_value) = &recipient.custom_value {
+        attributes.push((
+            "customValue".to_string(),
+            Value::Text(custom_value.to_string()),
+        ));
+    }
+
+    match (&recipient.custom_key, &recipient.custom_value) {
+        (Some(_), None) => {
+            errors.push(Error::MissingAttribute("customValue".to_string()));
+        }
+        (None, Some(_)) => {
+            errors.push(Error::MissingAttribute("customKey".to_string()));
+        }
+        (Some(custom_key), Some(custom_value)) => {
+            errors.extend(v4v::analyze_custom_record(custom_key, custom_value));
+        }
+        (None, None) => {}
+    }
+
+    if let Some(fee) = &recipient.fee {
+        match fee {
+            badpod::Bool::Ok(b) => {
+                attributes.push(("fee".to_string(), Value::Object(b.to_string())));
+            }
+            badpod::Bool::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "fee".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "valueRecipient".to_string()),
+        children: vec![],
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_location(location: &badpod::podcast::Location) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(value) = &location.value {
+        if value.chars().count() > 128 {
+            errors.push(Error::AttributeExceedsMaxLength(
+                NODE_VALUE.to_string(),
+                value.to_string(),
+                128,
+            ));
+        } else {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
+        }
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    if let Some(geo) = &location.geo {
+        match geo {
+            badpod::podcast::Geo::Ok {
+                latitude,
+                longitude,
+                altitude,
+                uncertainty,
+            } => {
+                let mut geo_str = format!("{{ latitude: {}, longitude: {}", latitude, longitude);
+                if let Some(altitude) = altitude {
+                    geo_str.push_str(format!(", altitude: {}", altitude).as_str());
+                }
+                if let Some(uncertainty) = uncertainty {
+                    geo_str.push_str(format!(", uncertainty: {}", uncertainty).as_str());
+                }
+                geo_str.push_str(" }");
+                attributes.push(("geo".to_string(), Value::Object(geo_str)));
+            }
+            badpod::podcast::Geo::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "geo".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(osm) = &location.osm {
+        match osm {
+            badpod::podcast::Osm::Ok {
+                type_,
+                id,
+                revision,
+            } => {
+                let mut osm_str = format!("{{ type: {:?}, id: {}", type_, id);
+                if let Some(revision) = revision {
+                    osm_str.push_str(format!(", revision: {}", revision).as_str());
+                }
+                osm_str.push_str(" }");
+                attributes.push(("osm".to_string(), Value::Object(osm_str)));
+            }
+            badpod::podcast::Osm::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "osm".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "location".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_guid(guid: &badpod::podcast::Guid) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match guid {
+        badpod::podcast::Guid::Ok(guid) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(guid.to_string())));
+        }
+        badpod::podcast::Guid::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "guid".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_medium(medium: &badpod::podcast::Medium) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match medium {
+        badpod::podcast::Medium::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        _ => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(medium.to_string())));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "medium".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_txt(txt: &badpod::podcast::Txt) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(value) = &txt.value {
+        attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    match &txt.purpose {
+        Some(badpod::podcast::TxtPurpose::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "purpose".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        Some(purpose) => {
+            attributes.push(("purpose".to_string(), Value::Object(purpose.to_string())));
+        }
+        None => {}
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "txt".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_block(block: &badpod::podcast::Block) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &block.value {
+        Some(badpod::Bool::Ok(b)) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(b.to_string())));
+        }
+        Some(badpod::Bool::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {
+            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+        }
+    }
+
+    if let Some(id) = &block.id {
+        match id {
+            badpod::podcast::Service::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "id".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("id".to_string(), Value::Object(id.to_string())));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "block".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_locked(locked: &badpod::podcast::Locked) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &locked.value {
+        Some(badpod::Bool::Ok(b)) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(b.to_string())));
+        }
+        Some(badpod::Bool::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {
+            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+        }
+    }
+
+    if let Some(owner) = &locked.owner {
+        attributes.push(("owner".to_string(), Value::Text(owner.to_string())));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "locked".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_funding(funding: &badpod::podcast::Funding) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(value) = &funding.value {
+        if value.len() > 128 {
+            errors.push(Error::AttributeExceedsMaxLength(
+                NODE_VALUE.to_string(),
+                value.to_string(),
+                128,
+            ));
+        } else {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
+        }
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    if let Some(url) = &funding.url {
+        match url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("url".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("url".to_string()));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "funding".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_person(person: &badpod::podcast::Person) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(name) = &person.value {
+        if name.len() > 128 {
+            errors.push(Error::AttributeExceedsMaxLength(
+                NODE_VALUE.to_string(),
+                name.to_string(),
+                128,
+            ));
+        } else {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(name.to_string())));
+        }
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    person::analyze_group_and_role(
+        person.group.as_ref(),
+        person.role.as_ref(),
+        &mut errors,
+        &mut attributes,
+    );
+
+    if let Some(image) = &person.img {
+        match image {
+            badpod::Url::Ok(url) => {
+                attributes.push(("img".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "img".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(href) = &person.href {
+        match href {
+            badpod::Url::Ok(url) => {
+                attributes.push(("href".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "href".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "person".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_trailer(trailer: &badpod::podcast::Trailer) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(tile) = &trailer.value {
+        if tile.len() > 128 {
+            errors.push(Error::AttributeExceedsMaxLength(
+                NODE_VALUE.to_string(),
+                tile.to_string(),
+                128,
+            ));
+        } else {
+            attributes.push((NODE_VALUE.to_string(), Value::Text(tile.to_string())));
+        }
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    if let Some(url) = &trailer.url {
+        match url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("url".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("url".to_string()));
+    }
+
+    if let Some(pub_date) = &trailer.pub_date {
+        match pub_date {
+            badpod::DateTime::Ok(dt) => {
+                attributes.push(("pubDate".to_string(), Value::Object(dt.to_string())));
+            }
+            badpod::DateTime::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "pubDate".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(length) = &trailer.length {
+        match length {
+            badpod::Integer::Ok(i) => {
+                attributes.push(("length".to_string(), Value::Object(i.to_string())));
+            }
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "length".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(mimetype) = &trailer.type_ {
+        match mimetype {
+            badpod::MimeEnclosure::Other((s, _)) => {
+                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
+            }
+            _ => {
+                attributes.push(("type".to_string(), Value::Text(mimetype.to_string())));
+            }
+        }
+    }
+
+    if let Some(season) = &trailer.season {
+        match season {
+            badpod::Integer::Ok(i) => {
+                attributes.push(("season".to_string(), Value::Object(i.to_string())));
+            }
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "season".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "trailer".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_license(license: &badpod::podcast::License) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(url) = &license.url {
+        match url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("url".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    match &license.value {
+        Some(value) => {
+            let name = match value {
+                badpod::podcast::LicenseType::Other((s, _)) => s.to_string(),
+                _ => license::spdx_name(&value.to_string()),
+            };
+            if name.len() > 128 {
+                errors.push(Error::AttributeExceedsMaxLength(
+                    NODE_VALUE.to_string(),
+                    name.to_string(),
+                    128,
+                ));
+            } else {
+                attributes.push((NODE_VALUE.to_string(), Value::Text(name.to_string())));
+            }
+            license::analyze_spdx(&name, license.url.is_some(), &mut errors, &mut attributes);
+        }
+        None => {
+            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "license".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_images(images: &badpod::podcast::Images) -> Node {
+    let mut attributes = Vec::new();
+    let mut errors = Vec::new();
+
+    match &images.srcset {
+        badpod::podcast::ImageSrcSet::Ok(image_data) => {
+            let mut img_strs = Vec::new();
+            for (url, width) in image_data {
+                img_strs.push(format!("{{ url: \"{}\", width: {} }}", url, width));
+            }
+            let value = format!("[ {} ]", img_strs.join(", "));
+            attributes.push(("srcset".to_string(), Value::Object(value)));
+        }
+        badpod::podcast::ImageSrcSet::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "srcset".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "images".to_string()),
+        attributes,
+        errors,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_transcript(transcript: &badpod::podcast::Transcript) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(url) = &transcript.url {
+        match url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("url".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("url".to_string()));
+    }
+
+    if let Some(type_) = &transcript.type_ {
+        match type_ {
+            badpod::MimeTranscript::ApplicationSrt => {
+                errors.push(Error::CustomWithExtraInfo(
+                    Rule::SrtTranscriptType,
+                    "\"<code>application/srt</code>\" in attribute <code class=\"font-bold\">type</code> is not a valid mime type.".to_string(),
+                    "<a class=\"link\" href=\"https://github.com/Podcastindex-org/podcast-namespace/pull/331\" target=\"_blank\" rel=\"noopener noreferrer\">On February 3, 2022</a>, the recognized alternative for SubRip files in the podcast namespace specification became \"<code>application/x-subrip</code>\". However, keep in mind that although podcast players like Podverse and Podcast Addict have updated their codebases, some other apps may still only recognize \"<code>application/srt</code>\" at this time."
+                        .to_string(),
+                ));
+            }
+            badpod::MimeTranscript::Other((s, _)) => {
+                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
+            }
+            _ => {
+                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("type".to_string()));
+    }
+
+    if let Some(language) = &transcript.language {
+        match language {
+            badpod::Language::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "language".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("language".to_string(), Value::Object(language.to_string())));
+            }
+        }
+    }
+
+    if let Some(rel) = &transcript.rel {
+        match rel {
+            badpod::podcast::TranscriptRel::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "rel".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("rel".to_string(), Value::Object(rel.to_string())));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "transcript".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_chapters(chapters: &badpod::podcast::Chapters) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(url) = &chapters.url {
+        match url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("url".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "url".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("url".to_string()));
+    }
+
+    if let Some(type_) = &chapters.type_ {
+        match type_ {
+            badpod::MimeChapters::Other((s, _)) => {
+                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
+            }
+            _ => {
+                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("type".to_string()));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "chapters".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_soundbite(soundbite: &badpod::podcast::Soundbite) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &soundbite.start_time {
+        Some(badpod::Float::Ok(f)) => {
+            attributes.push(("startTime".to_string(), Value::Object(f.to_string())));
+        }
+        Some(badpod::Float::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "startTime".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("startTime".to_string()));
+        }
+    }
+
+    match &soundbite.duration {
+        Some(badpod::Float::Ok(f)) => {
+            attributes.push(("duration".to_string(), Value::Object(f.to_string())));
+        }
+        Some(badpod::Float::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "duration".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("duration".to_string()));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "soundbite".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_season(season: &badpod::podcast::Season) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &season.value {
+        Some(badpod::Integer::Ok(i)) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(i.to_string())));
+        }
+        Some(badpod::Integer::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => {
+            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+        }
+    }
+
+    if let Some(name) = &season.name {
+        attributes.push(("name".to_string(), Value::Text(name.to_string())));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "season".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_episode(episode: &badpod::podcast::Episode) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &episode.value {
+        Some(badpod::Number::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        Some(n) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(n.to_string())));
+        }
+        None => {
+            errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+        }
+    }
+
+    if let Some(display) = &episode.display {
+        attributes.push(("display".to_string(), Value::Text(display.to_string())));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "episode".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_alternate_enclosure(
+    alternate_enclosure: &badpod::podcast::AlternateEnclosure,
+) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+    let mut children = Vec::new();
+
+    if let Some(type_) = &alternate_enclosure.type_ {
+        match type_ {
+            badpod::MimeEnclosure::Other((s, _)) => {
+                errors.push(Error::InvalidAttribute("type".to_string(), s.to_string()));
+            }
+            _ => {
+                attributes.push(("type".to_string(), Value::Object(type_.to_string())));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("type".to_string()));
+    }
+
+    if let Some(length) = &alternate_enclosure.length {
+        match length {
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "length".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("length".to_string(), Value::Object(length.to_string())));
+            }
+        }
+    }
+
+    if let Some(bit_rate) = &alternate_enclosure.bit_rate {
+        match bit_rate {
+            badpod::Float::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "bitrate".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("bitrate".to_string(), Value::Object(bit_rate.to_string())));
+            }
+        }
+    }
+
+    if let Some(height) = &alternate_enclosure.height {
+        match height {
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "height".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("height".to_string(), Value::Object(height.to_string())));
+            }
+        }
+    }
+
+    if let Some(lang) = &alternate_enclosure.language {
+        attributes.push(("lang".to_string(), Value::Text(lang.to_string())));
+    }
+
+    if let Some(title) = &alternate_enclosure.title {
+        attributes.push(("title".to_string(), Value::Text(title.to_string())));
+    }
+
+    if let Some(rel) = &alternate_enclosure.rel {
+        if rel.len() > 32 {
+            errors.push(Error::AttributeExceedsMaxLength(
+                "rel".to_string(),
+                rel.to_string(),
+                32,
+            ));
+        } else {
+            attributes.push(("rel".to_string(), Value::Text(rel.to_string())));
+        }
+    }
+
+    if let Some(default) = &alternate_enclosure.default {
+        match default {
+            badpod::Bool::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "default".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("default".to_string(), Value::Object(default.to_string())));
+            }
+        }
+    }
+
+    for source in &alternate_enclosure.podcast_source {
+        children.push(analyze_podcast_source(source));
+    }
+    if children.is_empty() {
+        errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Podcast),
+            "source".to_string(),
+        )));
+    }
+
+    for integrity in &alternate_enclosure.podcast_integrity {
+        children.push(analyze_podcast_integrity(integrity));
+    }
+    if alternate_enclosure.podcast_integrity.len() > 1 {
+        errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Podcast),
+            "integrity".to_string(),
+        )));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "alternateEnclosure".to_string()),
+        errors,
+        attributes,
+        children,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_source(source: &badpod::podcast::Source) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(uri) = &source.uri {
+        match uri {
+            badpod::Url::Ok(url) => {
+                attributes.push(("uri".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "uri".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("uri".to_string()));
+    }
+
+    if let Some(content_type) = &source.type_ {
+        match content_type {
+            badpod::MimeEnclosure::Other((s, _)) => {
+                errors.push(Error::InvalidAttribute(
+                    "contentType".to_string(),
+                    s.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push((
+                    "contentType".to_string(),
+                    Value::Object(content_type.to_string()),
+                ));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "source".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_integrity(integrity: &badpod::podcast::Integrity) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &integrity.type_ {
+        Some(badpod::podcast::IntegrityType::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "type".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        Some(t) => {
+            attributes.push(("type".to_string(), Value::Object(t.to_string())));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("type".to_string()));
+        }
+    }
+
+    if let Some(value) = &integrity.value {
+        attributes.push(("value".to_string(), Value::Text(value.to_string())));
+    } else {
+        errors.push(Error::MissingAttribute("value".to_string()));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "integrity".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_social_interact(social_interact: &badpod::podcast::SocialInteract) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(uri) = &social_interact.uri {
+        match uri {
+            badpod::Url::Ok(url) => {
+                attributes.push(("uri".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "uri".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("uri".to_string()));
+    }
+
+    match &social_interact.protocol {
+        Some(badpod::podcast::SocialProtocol::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "protocol".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        Some(p) => {
+            attributes.push(("protocol".to_string(), Value::Object(p.to_string())));
+        }
+        None => {
+            errors.push(Error::MissingAttribute("protocol".to_string()));
+        }
+    }
+
+    if let Some(account_id) = &social_interact.account_id {
+        attributes.push(("accountId".to_string(), Value::Text(account_id.to_string())));
+    }
+
+    if let Some(account_url) = &social_interact.account_url {
+        match account_url {
+            badpod::Url::Ok(url) => {
+                attributes.push(("accountUrl".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "accountUrl".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(priority) = &social_interact.priority {
+        match priority {
+            badpod::Integer::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "priority".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+            _ => {
+                attributes.push(("priority".to_string(), Value::Object(priority.to_string())));
+            }
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "socialInteract".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+fn analyze_podcast_content_link(content_link: &badpod::podcast::ContentLink) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    if let Some(value) = &content_link.value {
+        attributes.push((NODE_VALUE.to_string(), Value::Text(value.to_string())));
+    } else {
+        errors.push(Error::MissingAttribute(NODE_VALUE.to_string()));
+    }
+
+    if let Some(href) = &content_link.href {
+        match href {
+            badpod::Url::Ok(url) => {
+                attributes.push(("href".to_string(), Value::Url(url.to_string())));
+            }
+            badpod::Url::Other((s, reason)) => {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "href".to_string(),
+                    s.to_string(),
+                    reason.to_string(),
+                ));
+            }
+        }
+    } else {
+        errors.push(Error::MissingAttribute("href".to_string()));
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Podcast), "contentLink".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_str() {
+        let report = validate_str(
+            r#"<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+                <channel>
+                    <title>Show</title>
+                    <podcast:locked>maybe</podcast:locked>
+                </channel>
+            </rss>"#,
+        )
+        .unwrap();
+        assert!(report.has_podcast_tags());
+        assert!(report.has_errors());
+
+        assert!(validate_str("not a feed").is_err());
+    }
+
+    /// Findings of the first child of `node` with the given name.
+    fn child_errors<'a>(node: &'a Node, name: &str) -> &'a [Error] {
+        &node
+            .children
+            .iter()
+            .find(|child| child.name.to_string() == name)
+            .unwrap()
+            .errors
+    }
+
+    fn rule(error: &Error) -> Option<Rule> {
+        match error {
+            Error::Custom(rule, _) | Error::CustomWithExtraInfo(rule, _, _) => Some(*rule),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_analyze_channel() {
+        let report = validate_str(
+            r#"<rss version="2.0"><channel><title>Show</title>
+                <link>ftp://example.com/</link>
+                <language>en</language>
+            </channel></rss>"#,
+        )
+        .unwrap();
+        let channel = &report.root.children[0];
+        assert!(matches!(
+            child_errors(channel, "link"),
+            [Error::InvalidAttributeWithReason(attr, value, _)]
+                if attr == NODE_VALUE && value == "ftp://example.com/"
+        ));
+        assert!(child_errors(channel, "language").is_empty());
+
+        let report = validate_str(
+            r#"<rss version="2.0"><channel><title>Show</title>
+                <link>not a link</link>
+                <language>not a language</language>
+            </channel></rss>"#,
+        )
+        .unwrap();
+        let channel = &report.root.children[0];
+        for name in ["link", "language"] {
+            assert!(matches!(
+                child_errors(channel, name),
+                [Error::InvalidAttributeWithReason(attr, value, _)]
+                    if attr == NODE_VALUE && value.starts_with("not a ")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_analyze_items() {
+        let tomorrow_and_more = (chrono::Utc::now() + chrono::Duration::days(2)).to_rfc2822();
+        let report = validate_str(&format!(
+            r#"<rss version="2.0"><channel><title>Show</title>
+                <item>
+                    <title>1</title>
+                    <enclosure url="https://example.com/1.mp3" length="0" type="audio/mpeg"/>
+                    <guid isPermaLink="false">episode-1</guid>
+                    <pubDate>Tue, 10 Jan 2023 08:00:00 GMT</pubDate>
+                </item>
+                <item>
+                    <enclosure/>
+                    <guid>https://example.com/2</guid>
+                    <pubDate>2023-01-10T08:00:00Z</pubDate>
+                </item>
+                <item>
+                    <enclosure url="ftp://example.com/3.mp3" length="many" type="audio/x-nonsense"/>
+                    <guid isPermaLink="maybe">episode-3</guid>
+                    <pubDate>{tomorrow_and_more}</pubDate>
+                </item>
+                <item>
+                    <enclosure url="https://example.com/4.mp3" length="-1" type="audio/mpeg"/>
+                    <guid>episode-4</guid>
+                    <pubDate>yesterday</pubDate>
+                </item>
+                <item><title>Nothing else</title></item>
+            </channel></rss>"#
+        ))
+        .unwrap();
+        let items: Vec<&Node> = report.root.children[0]
+            .children
+            .iter()
+            .filter(|child| child.name.to_string() == "item")
+            .collect();
+        let invalid = |error: &Error| match error {
+            Error::InvalidAttribute(attr, _) | Error::InvalidAttributeWithReason(attr, _, _) => {
+                attr.clone()
+            }
+            _ => panic!("unexpected finding {error:?}"),
+        };
+
+        // Zero length is allowed, and isPermaLink="false" allows any text.
+        let enclosure = child_errors(items[0], "enclosure");
+        assert_eq!(
+            enclosure.iter().map(rule).collect::<Vec<_>>(),
+            [Some(Rule::EnclosureZeroLength)]
+        );
+        assert!(child_errors(items[0], "guid").is_empty());
+        assert!(child_errors(items[0], "pubDate").is_empty());
+        assert!(items[0].errors.is_empty());
+
+        assert_eq!(
+            child_errors(items[1], "enclosure"),
+            ["url", "length", "type"].map(|attr| Error::MissingAttribute(attr.to_string()))
+        );
+        assert!(child_errors(items[1], "guid").is_empty());
+        // ISO 8601 dates are parsed by badpod but are not RFC 2822.
+        assert_eq!(
+            child_errors(items[1], "pubDate")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            [NODE_VALUE]
+        );
+
+        assert_eq!(
+            child_errors(items[2], "enclosure")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            ["url", "length", "type"]
+        );
+        assert_eq!(
+            child_errors(items[2], "guid")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            ["isPermaLink"]
+        );
+        assert_eq!(
+            child_errors(items[2], "pubDate")
+                .iter()
+                .map(rule)
+                .collect::<Vec<_>>(),
+            [Some(Rule::FutureDate)]
+        );
+
+        assert_eq!(
+            child_errors(items[3], "enclosure")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            ["length"]
+        );
+        // Without isPermaLink="false", the GUID must be a URL.
+        assert_eq!(
+            child_errors(items[3], "guid")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            [NODE_VALUE]
+        );
+        assert_eq!(
+            child_errors(items[3], "pubDate")
+                .iter()
+                .map(invalid)
+                .collect::<Vec<_>>(),
+            [NODE_VALUE]
+        );
+
+        assert_eq!(
+            items[4].errors.iter().map(rule).collect::<Vec<_>>(),
+            [
+                Some(Rule::ItemMissingEnclosure),
+                Some(Rule::ItemMissingGuid),
+                Some(Rule::ItemMissingPubDate)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_severities() {
+        let node = |errors: Vec<Error>, children: Vec<Node>| Node {
+            errors,
+            children,
+            ..Default::default()
+        };
+        let info = || Error::Custom(Rule::EnclosureZeroLength, String::new());
+        let warning = || Error::Custom(Rule::ItemMissingEnclosure, String::new());
+        let error = || Error::MissingAttribute("url".to_string());
+
+        let root = node(
+            vec![info()],
+            vec![
+                node(vec![warning(), info()], vec![]),
+                node(
+                    vec![Error::Downgraded(Severity::Info, Box::new(error()))],
+                    vec![node(vec![error()], vec![])],
+                ),
+            ],
+        );
+        assert_eq!(root.max_severity(), Some(Severity::Error));
+        assert_eq!(root.children[0].max_severity(), Some(Severity::Warning));
+        assert_eq!(node(vec![], vec![]).max_severity(), None);
+        assert_eq!(
+            [Severity::Error, Severity::Warning, Severity::Info].map(|s| root.count(s)),
+            [1, 1, 3]
+        );
+        assert_eq!(root.children[1].count(Severity::Error), 1);
+    }
+
+    #[test]
+    fn test_report_json() {
+        let report = validate_str(
+            r#"<rss version="2.0"><channel><title>Show</title><item><guid>a</guid></item></channel></rss>"#,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""item-missing-enclosure""#));
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn test_analyze_rss() {
+        let report = validate_str(r#"<rss version="2.0"></rss>"#).unwrap();
+        assert!(report
+            .root
+            .errors
+            .contains(&Error::MissingChild(TagName(None, "channel".to_string()))));
+    }
+
+    #[test]
+    fn test_error_to_string() {
+        let error = Error::Custom(
+            Rule::ItunesOwnerMissingEmail,
+            "Missing child <code>&lt;itunes:email&gt;</code>, write <code>&amp;lt;</code> for “&#60;” &amp; “&#x3E;”.".to_string(),
+        );
+        assert_eq!(
+            error.to_string(),
+            "Missing child <itunes:email>, write &lt; for “<” & “>”."
+        );
+    }
+
+    #[test]
+    fn test_analyze_live_item_schedule() {
+        use badpod::podcast::{LiveItem, LiveItemStatus};
+
+        let at = |s: &str| chrono::DateTime::parse_from_rfc3339(s).unwrap();
+        let now = at("2023-06-01T12:00:00Z").with_timezone(&chrono::Utc);
+        let rules = |status, start: &str, end: &str| -> Vec<Rule> {
+            let item = LiveItem {
+                status: Some(status),
+                start: Some(badpod::DateTime::Ok(at(start))),
+                end: Some(badpod::DateTime::Ok(at(end))),
+                ..Default::default()
+            };
+            analyze_live_item_schedule(&item, now)
+                .into_iter()
+                .map(|error| match error {
+                    Error::Custom(rule, _) => rule,
+                    _ => panic!("unexpected finding {error:?}"),
+                })
+                .collect()
+        };
+
+        assert!(rules(
+            LiveItemStatus::Live,
+            "2023-06-01T12:30:00Z",
+            "2023-06-01T14:00:00Z"
+        )
+        .is_empty());
+        assert_eq!(
+            rules(
+                LiveItemStatus::Live,
+                "2023-06-01T14:00:00Z",
+                "2023-06-01T13:00:00Z"
+            ),
+            [Rule::LiveItemEndBeforeStart, Rule::LiveItemLiveBeforeStart]
+        );
+        assert_eq!(
+            rules(
+                LiveItemStatus::Live,
+                "2023-06-01T08:00:00Z",
+                "2023-06-01T10:00:00Z"
+            ),
+            [Rule::LiveItemLiveAfterEnd]
+        );
+        assert!(rules(
+            LiveItemStatus::Pending,
+            "2023-05-31T18:00:00Z",
+            "2023-05-31T20:00:00Z"
+        )
+        .is_empty());
+        assert_eq!(
+            rules(
+                LiveItemStatus::Pending,
+                "2023-05-30T18:00:00Z",
+                "2023-05-30T20:00:00Z"
+            ),
+            [Rule::LiveItemStalePending]
+        );
+        assert_eq!(
+            rules(
+                LiveItemStatus::Ended,
+                "2023-05-30T18:00:00Z",
+                "2023-05-30T20:00:00Z"
+            ),
+            [Rule::LiveItemEndedWithoutReplay]
+        );
+    }
+}
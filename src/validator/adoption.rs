@@ -0,0 +1,181 @@
+//! Which tags of the podcast namespace a feed uses, grouped by the phase in which they were
+//! adopted.
+//!
+//! Only the tags that the validator analyzes are covered.
+
+use super::rules::PODCAST_SPEC;
+use super::{Node, Report};
+
+/// Where a tag may appear.
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Level {
+    Channel,
+    Item,
+    Both,
+}
+
+/// Tags by the phase of the namespace in which they were adopted.
+const PHASES: [&[(&str, Level)]; 6] = [
+    &[
+        ("locked", Level::Channel),
+        ("transcript", Level::Item),
+        ("funding", Level::Channel),
+        ("chapters", Level::Item),
+        ("soundbite", Level::Item),
+    ],
+    &[
+        ("person", Level::Both),
+        ("location", Level::Both),
+        ("season", Level::Item),
+        ("episode", Level::Item),
+    ],
+    &[
+        ("trailer", Level::Channel),
+        ("license", Level::Both),
+        ("alternateEnclosure", Level::Item),
+        ("guid", Level::Channel),
+        ("value", Level::Both),
+    ],
+    &[
+        ("medium", Level::Channel),
+        ("images", Level::Both),
+        ("liveItem", Level::Channel),
+    ],
+    &[("socialInteract", Level::Item), ("block", Level::Channel)],
+    &[("txt", Level::Both)],
+];
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Adoption {
+    pub items: usize,
+    pub phases: Vec<Phase>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Phase {
+    /// Starting from 1.
+    pub number: usize,
+    pub tags: Vec<TagAdoption>,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct TagAdoption {
+    /// Name without the prefix, e.g. `locked`.
+    pub name: &'static str,
+    pub spec_url: String,
+    /// Whether the channel has the tag, unless the tag does not belong in the channel.
+    pub in_channel: Option<bool>,
+    /// Number of items that have the tag, unless the tag does not belong in items.
+    pub in_items: Option<usize>,
+}
+
+impl TagAdoption {
+    pub fn is_used(&self) -> bool {
+        self.in_channel == Some(true) || self.in_items.is_some_and(|n| n > 0)
+    }
+}
+
+pub fn adoption(report: &Report) -> Adoption {
+    let channel = report
+        .root
+        .children
+        .iter()
+        .find(|n| n.name.to_string() == "channel");
+    let items: Vec<&Node> = channel
+        .into_iter()
+        .flat_map(|c| c.children.iter())
+        .filter(|n| n.name.to_string() == "item")
+        .collect();
+    let has = |node: &Node, name: &str| {
+        let name = format!("podcast:{name}");
+        node.children.iter().any(|n| n.name.to_string() == name)
+    };
+
+    let phases = PHASES
+        .iter()
+        .enumerate()
+        .map(|(i, tags)| Phase {
+            number: i + 1,
+            tags: tags
+                .iter()
+                .map(|(name, level)| TagAdoption {
+                    name,
+                    spec_url: format!("{PODCAST_SPEC}#{}", anchor(name)),
+                    in_channel: match level {
+                        Level::Item => None,
+                        _ => Some(channel.is_some_and(|c| has(c, name))),
+                    },
+                    in_items: match level {
+                        Level::Channel => None,
+                        _ => Some(items.iter().filter(|item| has(item, name)).count()),
+                    },
+                })
+                .collect(),
+        })
+        .collect();
+
+    Adoption {
+        items: items.len(),
+        phases,
+    }
+}
+
+/// Section of the specification, e.g. `alternate-enclosure` for `alternateEnclosure`.
+fn anchor(name: &str) -> String {
+    let mut anchor = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            anchor.push('-');
+        }
+        anchor.push(c.to_ascii_lowercase());
+    }
+    anchor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Namespace, TagName};
+    use super::*;
+
+    #[test]
+    fn test_adoption() {
+        let node = |name: TagName, children: Vec<Node>| Node {
+            name,
+            children,
+            ..Default::default()
+        };
+        let podcast =
+            |name: &str| node(TagName(Some(Namespace::Podcast), name.to_string()), vec![]);
+        let tag = |name: &str, children| node(TagName(None, name.to_string()), children);
+        let root = tag(
+            "rss",
+            vec![tag(
+                "channel",
+                vec![
+                    podcast("locked"),
+                    tag("item", vec![podcast("transcript"), podcast("person")]),
+                    tag("item", vec![podcast("transcript")]),
+                ],
+            )],
+        );
+        let adoption = adoption(&Report { root });
+
+        assert_eq!(adoption.items, 2);
+        let phase_1 = &adoption.phases[0].tags;
+        assert_eq!(
+            (phase_1[0].in_channel, phase_1[0].in_items),
+            (Some(true), None)
+        );
+        assert_eq!(
+            (phase_1[1].in_channel, phase_1[1].in_items),
+            (None, Some(2))
+        );
+        assert!(!phase_1[2].is_used());
+        let person = &adoption.phases[1].tags[0];
+        assert_eq!((person.in_channel, person.in_items), (Some(false), Some(1)));
+        assert_eq!(
+            adoption.phases[2].tags[2].spec_url,
+            "https://podcastindex.org/namespace/1.0#alternate-enclosure"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Checks that need to look at all items or live items of a channel at once.
+
+use super::{escape_html, guid_str, Error, Namespace, Node, Rule, TagName};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Detects collisions between the items of a channel and reports them on every involved item.
+///
+/// `node` must be the result of analyzing `channel`, so that its `item` children are in the same
+/// order as `channel.item`.
+pub(super) fn analyze_items(channel: &badpod::Channel, node: &mut Node) {
+    let mut findings: Vec<Vec<Error>> = vec![Vec::new(); channel.item.len()];
+
+    let guids = channel.item.iter().map(|item| {
+        item.guid
+            .first()
+            .and_then(|guid| guid.value.as_ref())
+            .map(|value| guid_str(value).to_string())
+    });
+    for (value, positions) in collisions(guids) {
+        for &i in &positions {
+            findings[i].push(Error::Custom(
+                Rule::DuplicateGuid,
+                format!(
+                    "GUID “<code>{}</code>” is also used by {}, but it must uniquely identify an item.",
+                    escape_html(&value),
+                    others(&positions, i)
+                ),
+            ));
+        }
+    }
+
+    let episodes = channel.item.iter().map(|item| {
+        let episode = match item.podcast_episode.first().and_then(|e| e.value.as_ref()) {
+            Some(badpod::Number::Other(_)) | None => return None,
+            Some(n) => n.to_string(),
+        };
+        let season = match item.podcast_season.first().and_then(|s| s.value.as_ref()) {
+            Some(badpod::Integer::Ok(i)) => Some(*i),
+            Some(badpod::Integer::Other(_)) => return None,
+            None => None,
+        };
+        Some((season, episode))
+    });
+    for ((season, episode), positions) in collisions(episodes) {
+        let season = match season {
+            Some(season) => format!(" of season {season}"),
+            None => String::new(),
+        };
+        for &i in &positions {
+            findings[i].push(Error::Custom(
+                Rule::DuplicateEpisodeNumber,
+                format!(
+                    "Episode {}{} is also claimed by {}.",
+                    escape_html(&episode),
+                    season,
+                    others(&positions, i)
+                ),
+            ));
+        }
+    }
+
+    let enclosure_urls = channel
+        .item
+        .iter()
+        .map(|item| match item.enclosure.first() {
+            Some(badpod::Enclosure {
+                url: Some(badpod::Url::Ok(url)),
+                ..
+            }) => Some(url.to_string()),
+            _ => None,
+        });
+    for (url, positions) in collisions(enclosure_urls) {
+        for &i in &positions {
+            findings[i].push(Error::Custom(
+                Rule::DuplicateEnclosureUrl,
+                format!(
+                    "Enclosure URL “<code>{}</code>” is also used by {}. Apps may treat the items as the same episode.",
+                    escape_html(&url),
+                    others(&positions, i)
+                ),
+            ));
+        }
+    }
+
+    let item_name = TagName(None, "item".to_string());
+    let item_nodes = node
+        .children
+        .iter_mut()
+        .filter(|child| child.name == item_name);
+    for (item_node, errors) in item_nodes.zip(findings) {
+        item_node.errors.extend(errors);
+    }
+}
+
+/// Reports live items whose scheduled times overlap.
+pub(super) fn analyze_live_items(channel: &badpod::Channel, node: &mut Node) {
+    let windows: Vec<_> = channel
+        .podcast_live_item
+        .iter()
+        .map(|item| match (&item.start, &item.end) {
+            (Some(badpod::DateTime::Ok(start)), Some(badpod::DateTime::Ok(end))) if start < end => {
+                Some((*start, *end))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut findings: Vec<Vec<Error>> = vec![Vec::new(); windows.len()];
+    for (i, window) in windows.iter().enumerate() {
+        let (start, end) = match window {
+            Some(x) => x,
+            None => continue,
+        };
+        let overlapping: Vec<usize> = windows
+            .iter()
+            .enumerate()
+            .filter_map(|(j, other)| match other {
+                Some((other_start, other_end))
+                    if j != i && start < other_end && other_start < end =>
+                {
+                    Some(j)
+                }
+                _ => None,
+            })
+            .collect();
+        if !overlapping.is_empty() {
+            let mut positions = overlapping;
+            positions.push(i);
+            findings[i].push(Error::Custom(
+                Rule::OverlappingLiveItems,
+                format!(
+                    "Scheduled time overlaps with live {}.",
+                    others(&positions, i)
+                ),
+            ));
+        }
+    }
+
+    let live_item_name = TagName(Some(Namespace::Podcast), "liveItem".to_string());
+    let live_item_nodes = node
+        .children
+        .iter_mut()
+        .filter(|child| child.name == live_item_name);
+    for (live_item_node, errors) in live_item_nodes.zip(findings) {
+        live_item_node.errors.extend(errors);
+    }
+}
+
+/// Groups the positions of equal keys, keeping only the groups with more than one position, in the
+/// order in which they first appear.
+fn collisions<K: Hash + Eq>(keys: impl Iterator<Item = Option<K>>) -> Vec<(K, Vec<usize>)> {
+    let mut groups: HashMap<K, Vec<usize>> = HashMap::new();
+    for (i, key) in keys.enumerate().filter_map(|(i, key)| Some((i, key?))) {
+        groups.entry(key).or_default().push(i);
+    }
+    let mut groups: Vec<(K, Vec<usize>)> = groups
+        .into_iter()
+        .filter(|(_, positions)| positions.len() > 1)
+        .collect();
+    groups.sort_by_key(|(_, positions)| positions[0]);
+    groups
+}
+
+/// Describes the items at `positions` other than `current`, e.g. “items 2 and 5”.
+fn others(positions: &[usize], current: usize) -> String {
+    let numbers: Vec<String> = positions
+        .iter()
+        .filter(|&&i| i != current)
+        .map(|i| (i + 1).to_string())
+        .collect();
+    match numbers.as_slice() {
+        [n] => format!("item {n}"),
+        [rest @ .., last] => format!("items {} and {}", rest.join(", "), last),
+        [] => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::validate_str;
+    use super::*;
+
+    #[test]
+    fn test_collisions() {
+        let keys = vec![Some("a"), None, Some("b"), Some("a"), None, Some("a")];
+        assert_eq!(collisions(keys.into_iter()), vec![("a", vec![0, 3, 5])]);
+        let keys = vec![Some("b"), Some("a"), Some("a"), Some("b"), Some("c")];
+        assert_eq!(
+            collisions(keys.into_iter()),
+            vec![("b", vec![0, 3]), ("a", vec![1, 2])]
+        );
+
+        assert_eq!(others(&[0, 3], 0), "item 4");
+        assert_eq!(others(&[0, 3, 5], 3), "items 1 and 6");
+        assert_eq!(others(&[0, 1, 3, 5], 5), "items 1, 2 and 4");
+    }
+
+    #[test]
+    fn test_analyze_items_escapes_values() {
+        let item = r#"<item><title>Episode</title><guid isPermaLink="false">&lt;img src=x onerror=alert(1)&gt;</guid></item>"#;
+        let report = validate_str(&format!(
+            "<rss version=\"2.0\"><channel><title>Show</title>{item}{item}</channel></rss>"
+        ))
+        .unwrap();
+        let errors = &report.root.children[0].children[1].errors;
+        assert_eq!(
+            errors.last(),
+            Some(&Error::Custom(
+                Rule::DuplicateGuid,
+                "GUID “<code>&lt;img src=x onerror=alert(1)&gt;</code>” is also used by item 2, but it must uniquely identify an item.".to_string()
+            ))
+        );
+    }
+}
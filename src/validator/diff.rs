@@ -0,0 +1,250 @@
+//! Comparison of the analyses of two versions of a feed.
+
+use super::rules::rule_id;
+use super::{Error, Node, Report, TagName, Value, NODE_VALUE};
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Difference between the two versions of a node.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDiff {
+    /// Path of the node, e.g. `rss[1]/channel[1]/item[guid="abc"]/podcast:transcript[1]`.
+    pub path: String,
+    pub change: Change,
+    /// Attributes whose values differ, with the old and the new value.
+    pub attributes: Vec<(String, Option<Value>, Option<Value>)>,
+    /// Findings only present in the new version. For added nodes, these include the findings of
+    /// all descendants.
+    pub introduced: Vec<Error>,
+    /// Findings only present in the old version. For removed nodes, these include the findings of
+    /// all descendants.
+    pub resolved: Vec<Error>,
+}
+
+/// Compares two reports.
+///
+/// Nodes are matched by their tag path. Items are matched by their GUID when they have one, and
+/// other nodes by their order among the siblings with the same name.
+pub fn diff(old: &Report, new: &Report) -> Vec<NodeDiff> {
+    let mut diffs = Vec::new();
+    let path = format!("{}[1]", old.root.name);
+    if old.root.name == new.root.name {
+        diff_nodes(&old.root, &new.root, &path, &mut diffs);
+    } else {
+        diffs.push(removed(&old.root, path));
+        diffs.push(added(&new.root, format!("{}[1]", new.root.name)));
+    }
+    diffs
+}
+
+fn diff_nodes(old: &Node, new: &Node, path: &str, diffs: &mut Vec<NodeDiff>) {
+    let mut attributes = Vec::new();
+    for (name, old_value) in &old.attributes {
+        let new_value = find_attribute(new, name);
+        if new_value != Some(old_value) {
+            attributes.push((name.clone(), Some(old_value.clone()), new_value.cloned()));
+        }
+    }
+    for (name, new_value) in &new.attributes {
+        if find_attribute(old, name).is_none() {
+            attributes.push((name.clone(), None, Some(new_value.clone())));
+        }
+    }
+
+    let introduced = subtract(new, old);
+    let resolved = subtract(old, new);
+    if !attributes.is_empty() || !introduced.is_empty() || !resolved.is_empty() {
+        diffs.push(NodeDiff {
+            path: path.to_string(),
+            change: Change::Changed,
+            attributes,
+            introduced,
+            resolved,
+        });
+    }
+
+    let old_keys = child_keys(old);
+    let new_keys = child_keys(new);
+    for (old_child, key) in old.children.iter().zip(&old_keys) {
+        let child_path = format!("{path}/{key}");
+        match new_keys.iter().position(|k| k == key) {
+            Some(i) => diff_nodes(old_child, &new.children[i], &child_path, diffs),
+            None => diffs.push(removed(old_child, child_path)),
+        }
+    }
+    for (new_child, key) in new.children.iter().zip(&new_keys) {
+        if !old_keys.contains(key) {
+            diffs.push(added(new_child, format!("{path}/{key}")));
+        }
+    }
+}
+
+fn added(node: &Node, path: String) -> NodeDiff {
+    NodeDiff {
+        path,
+        change: Change::Added,
+        attributes: node
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.clone(), None, Some(value.clone())))
+            .collect(),
+        introduced: all_errors(node),
+        resolved: Vec::new(),
+    }
+}
+
+fn removed(node: &Node, path: String) -> NodeDiff {
+    NodeDiff {
+        path,
+        change: Change::Removed,
+        attributes: node
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.clone(), Some(value.clone()), None))
+            .collect(),
+        introduced: Vec::new(),
+        resolved: all_errors(node),
+    }
+}
+
+fn find_attribute<'a>(node: &'a Node, name: &str) -> Option<&'a Value> {
+    node.attributes
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value)
+}
+
+fn all_errors(node: &Node) -> Vec<Error> {
+    let mut errors = node.errors.clone();
+    for child in &node.children {
+        errors.extend(all_errors(child));
+    }
+    errors
+}
+
+/// Errors of `a` that are not in `b`, counting repeated errors separately.
+///
+/// Errors are compared by rule rather than by message, since messages quote values that may
+/// change without the problem being fixed.
+fn subtract(a: &Node, b: &Node) -> Vec<Error> {
+    let mut remaining: Vec<String> = b.errors.iter().map(|e| rule_id(b, e)).collect();
+    let mut result = Vec::new();
+    for error in &a.errors {
+        let id = rule_id(a, error);
+        match remaining.iter().position(|r| *r == id) {
+            Some(i) => {
+                remaining.remove(i);
+            }
+            None => result.push(error.clone()),
+        }
+    }
+    result
+}
+
+/// Path segments that identify the children of `node`.
+fn child_keys(node: &Node) -> Vec<String> {
+    let mut bases: Vec<(String, bool)> = Vec::new();
+    let mut keys = Vec::new();
+    for child in &node.children {
+        let base = match item_guid(child) {
+            Some(guid) => (format!("{}[guid=\"{}\"]", child.name, guid), true),
+            None => (child.name.to_string(), false),
+        };
+        let occurrence = bases.iter().filter(|b| **b == base).count() + 1;
+        keys.push(match base {
+            // Items are only numbered if their GUID is not unique.
+            (ref s, true) if occurrence == 1 => s.clone(),
+            (ref s, _) => format!("{s}[{occurrence}]"),
+        });
+        bases.push(base);
+    }
+    keys
+}
+
+fn item_guid(node: &Node) -> Option<&str> {
+    if node.name != TagName(None, "item".to_string()) {
+        return None;
+    }
+    let guid = node
+        .children
+        .iter()
+        .find(|child| child.name == TagName(None, "guid".to_string()))?;
+    match find_attribute(guid, NODE_VALUE) {
+        Some(Value::Text(value)) | Some(Value::Url(value)) => Some(value.as_str()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rules::Rule;
+    use super::*;
+
+    fn node(name: &str, children: Vec<Node>) -> Node {
+        Node {
+            name: TagName(None, name.to_string()),
+            children,
+            ..Default::default()
+        }
+    }
+
+    fn item(guid: &str, errors: Vec<Error>) -> Node {
+        let mut guid_node = node("guid", vec![]);
+        guid_node
+            .attributes
+            .push((NODE_VALUE.to_string(), Value::Text(guid.to_string())));
+        Node {
+            errors,
+            ..node("item", vec![guid_node])
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let missing_title = Error::MissingChild(TagName(None, "title".to_string()));
+        let duplicate = |value: &str| Error::Custom(Rule::DuplicateGuid, value.to_string());
+        let old = Report {
+            root: node(
+                "rss",
+                vec![node(
+                    "channel",
+                    vec![
+                        item("a", vec![missing_title.clone()]),
+                        item("b", vec![duplicate("x")]),
+                        item("d", vec![]),
+                    ],
+                )],
+            ),
+        };
+        let new = Report {
+            root: node(
+                "rss",
+                vec![node(
+                    "channel",
+                    vec![
+                        item("c", vec![duplicate("x")]),
+                        item("a", vec![]),
+                        item("b", vec![duplicate("y")]),
+                    ],
+                )],
+            ),
+        };
+
+        let diffs = diff(&old, &new);
+        assert_eq!(diffs.len(), 3);
+        assert_eq!(diffs[0].path, "rss[1]/channel[1]/item[guid=\"a\"]");
+        assert_eq!(diffs[0].change, Change::Changed);
+        assert_eq!(diffs[0].resolved, vec![missing_title]);
+        // Only the message of the finding in item "b" changed.
+        assert_eq!(diffs[1].path, "rss[1]/channel[1]/item[guid=\"d\"]");
+        assert_eq!(diffs[1].change, Change::Removed);
+        assert_eq!(diffs[2].path, "rss[1]/channel[1]/item[guid=\"c\"]");
+        assert_eq!(diffs[2].change, Change::Added);
+    }
+}
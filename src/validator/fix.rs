@@ -0,0 +1,277 @@
+//! Mechanical fixes for some of the findings.
+//!
+//! Fixes are edits of the source rather than of the parsed feed, so that the rest of the feed,
+//! including its formatting and comments, stays as it was.
+
+use super::rules::Rule;
+use super::source::Span;
+use super::{Error, Namespace, Node, TagName, NODE_VALUE};
+
+/// Replacement of a region of the source that resolves a finding.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Fix {
+    pub description: String,
+    /// Byte offset of the first replaced character.
+    pub start: usize,
+    /// Byte offset after the last replaced character.
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl Fix {
+    /// Returns the source of the element with the fix applied.
+    pub fn fixed_element(&self, source: &str, element: Span) -> String {
+        format!(
+            "{}{}{}",
+            &source[element.start..self.start],
+            self.replacement,
+            &source[self.end..element.end]
+        )
+    }
+}
+
+/// Returns a fix for the finding, if it is safe to make one without knowing more about the feed.
+///
+/// The node must have been analyzed from `source`, see [`super::validate_str`].
+pub fn suggest(node: &Node, error: &Error, source: &str) -> Option<Fix> {
+    let span = match node.span {
+        Some(span) if span.end <= source.len() => span,
+        _ => return None,
+    };
+
+    match error {
+        Error::Downgraded(_, error) => suggest(node, error, source),
+        Error::CustomWithExtraInfo(Rule::SrtTranscriptType, _, _) => {
+            let (start, end) = attribute_value_range(node, source, "type")?;
+            Some(Fix {
+                description: "Use the registered mime type for SubRip files.".to_string(),
+                start,
+                end,
+                replacement: "application/x-subrip".to_string(),
+            })
+        }
+        Error::AttributeExceedsMaxLength(attr, value, max_len)
+            if attr == NODE_VALUE
+                && node.name == TagName(Some(Namespace::Podcast), "location".to_string()) =>
+        {
+            truncate_value(node, source, value, *max_len)
+        }
+        Error::MissingAttribute(attr) if attr == "type" => {
+            let (start, end) = attribute_value_range(node, source, "url")?;
+            let mime_type = infer_mime_type(&node.name, &source[start..end])?;
+            let at = span.start + 1 + tag_name_len(&source[span.start + 1..]);
+            Some(Fix {
+                description: format!(
+                    "Add the mime type that corresponds to the file extension of the URL, \"{mime_type}\"."
+                ),
+                start: at,
+                end: at,
+                replacement: format!(" type=\"{mime_type}\""),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Applies the fixes of all findings in the tree, returning the fixed source and the number of
+/// fixes applied.
+///
+/// Fixes that overlap an already applied one are skipped.
+pub fn apply_all(root: &Node, source: &str) -> (String, usize) {
+    let mut fixes = Vec::new();
+    collect(root, source, &mut fixes);
+    fixes.sort_by_key(|fix| (fix.start, fix.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut applied = 0;
+    let mut i = 0;
+    for fix in fixes {
+        if fix.start < i {
+            continue;
+        }
+        fixed.push_str(&source[i..fix.start]);
+        fixed.push_str(&fix.replacement);
+        i = fix.end;
+        applied += 1;
+    }
+    fixed.push_str(&source[i..]);
+
+    (fixed, applied)
+}
+
+fn collect(node: &Node, source: &str, fixes: &mut Vec<Fix>) {
+    fixes.extend(
+        node.errors
+            .iter()
+            .filter_map(|error| suggest(node, error, source)),
+    );
+    for child in &node.children {
+        collect(child, source, fixes);
+    }
+}
+
+/// Byte range of the value of the attribute in the source, without the quotes.
+fn attribute_value_range(node: &Node, source: &str, name: &str) -> Option<(usize, usize)> {
+    let (_, span) = node.attribute_spans.iter().find(|(n, _)| n == name)?;
+    let text = source.get(span.start..span.end)?;
+    let quote = text.find(['"', '\''])?;
+    let start = span.start + quote + 1;
+    let end = span.end - 1;
+    if end < start || !text.ends_with(&text[quote..quote + 1]) {
+        return None;
+    }
+    Some((start, end))
+}
+
+fn truncate_value(node: &Node, source: &str, value: &str, max_len: usize) -> Option<Fix> {
+    let (_, span) = node
+        .attribute_spans
+        .iter()
+        .find(|(name, _)| name == NODE_VALUE)?;
+    let raw = source.get(span.start..span.end)?;
+    // Entities and CDATA sections would make the source longer than the value.
+    if raw.contains(['&', '<']) || raw.trim() != value {
+        return None;
+    }
+
+    // The maximum length is in characters, so the value is cut on the boundary of the first one
+    // that does not fit.
+    let (len, _) = value.char_indices().nth(max_len)?;
+    let start = span.start + (raw.len() - raw.trim_start().len());
+    Some(Fix {
+        description: format!("Shorten the value to {max_len} characters."),
+        start,
+        end: start + value.len(),
+        replacement: value[..len].trim_end().to_string(),
+    })
+}
+
+fn tag_name_len(s: &str) -> usize {
+    s.find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+        .unwrap_or(s.len())
+}
+
+fn infer_mime_type(name: &TagName, url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let (_, extension) = path.rsplit_once('.')?;
+    if extension.contains('/') {
+        return None;
+    }
+    let extension = extension.to_lowercase();
+
+    match (&name.0, name.1.as_str()) {
+        (None, "enclosure") => match extension.as_str() {
+            "mp3" => Some("audio/mpeg"),
+            "m4a" => Some("audio/x-m4a"),
+            "aac" => Some("audio/aac"),
+            "ogg" | "oga" => Some("audio/ogg"),
+            "opus" => Some("audio/opus"),
+            "flac" => Some("audio/flac"),
+            "wav" => Some("audio/wav"),
+            "mp4" => Some("video/mp4"),
+            "m4v" => Some("video/x-m4v"),
+            "mov" => Some("video/quicktime"),
+            "webm" => Some("video/webm"),
+            _ => None,
+        },
+        (Some(Namespace::Podcast), "transcript") => match extension.as_str() {
+            "srt" => Some("application/x-subrip"),
+            "vtt" => Some("text/vtt"),
+            "json" => Some("application/json"),
+            "html" | "htm" => Some("text/html"),
+            "txt" => Some("text/plain"),
+            _ => None,
+        },
+        (Some(Namespace::Podcast), "chapters") if extension == "json" => {
+            Some("application/json+chapters")
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_all() {
+        let location = "a".repeat(130);
+        let source = format!(
+            "<rss xmlns:podcast=\"https://podcastindex.org/namespace/1.0\"><channel><podcast:location>{location}</podcast:location><item><enclosure url=\"https://example.com/1.MP3?x=1\" length=\"1\"/><podcast:transcript url='https://example.com/1.srt' type='application/srt'/></item></channel></rss>"
+        );
+
+        let mut root = Node::default();
+        let element = super::super::source::parse(&source).unwrap();
+        root.name = TagName(None, "rss".to_string());
+        root.children = vec![Node {
+            name: TagName(None, "channel".to_string()),
+            children: vec![
+                Node {
+                    name: TagName(Some(Namespace::Podcast), "location".to_string()),
+                    errors: vec![Error::AttributeExceedsMaxLength(
+                        NODE_VALUE.to_string(),
+                        location.clone(),
+                        128,
+                    )],
+                    ..Default::default()
+                },
+                Node {
+                    name: TagName(None, "item".to_string()),
+                    children: vec![
+                        Node {
+                            name: TagName(None, "enclosure".to_string()),
+                            errors: vec![Error::MissingAttribute("type".to_string())],
+                            ..Default::default()
+                        },
+                        Node {
+                            name: TagName(Some(Namespace::Podcast), "transcript".to_string()),
+                            errors: vec![Error::CustomWithExtraInfo(
+                                Rule::SrtTranscriptType,
+                                String::new(),
+                                String::new(),
+                            )],
+                            ..Default::default()
+                        },
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }];
+        super::super::source::attach_spans(&mut root, &element);
+
+        let enclosure = &root.children[0].children[1].children[0];
+        let fix = suggest(enclosure, &enclosure.errors[0], &source).unwrap();
+        assert_eq!(
+            fix.fixed_element(&source, enclosure.span.unwrap()),
+            "<enclosure type=\"audio/mpeg\" url=\"https://example.com/1.MP3?x=1\" length=\"1\"/>"
+        );
+
+        let (fixed, applied) = apply_all(&root, &source);
+        assert_eq!(applied, 3);
+        assert!(fixed.contains(&format!(
+            "<podcast:location>{}</podcast:location>",
+            "a".repeat(128)
+        )));
+        assert!(fixed.contains("type='application/x-subrip'"));
+    }
+
+    #[test]
+    fn test_truncate_value() {
+        let location = "é".repeat(130);
+        let source = format!("<podcast:location>{location}</podcast:location>");
+        let mut node = Node {
+            name: TagName(Some(Namespace::Podcast), "location".to_string()),
+            ..Default::default()
+        };
+        super::super::source::attach_spans(
+            &mut node,
+            &super::super::source::parse(&source).unwrap(),
+        );
+
+        let fix = truncate_value(&node, &source, &location, 128).unwrap();
+        assert_eq!(fix.replacement, "é".repeat(128));
+        assert_eq!(fix.end, source.len() - "</podcast:location>".len());
+        assert!(truncate_value(&node, &source, &location, 130).is_none());
+    }
+}
@@ -0,0 +1,566 @@
+//! Analysis of the [Apple Podcasts](https://podcasters.apple.com/support/823-podcast-requirements)
+//! `itunes:` namespace.
+
+use super::{Error, Namespace, Node, Rule, TagName, Value, NODE_VALUE};
+
+/// Apple Podcasts categories and their subcategories.
+///
+/// See <https://podcasters.apple.com/support/1691-apple-podcasts-categories>.
+pub const CATEGORIES: [(&str, &[&str]); 19] = [
+    (
+        "Arts",
+        &[
+            "Books",
+            "Design",
+            "Fashion & Beauty",
+            "Food",
+            "Performing Arts",
+            "Visual Arts",
+        ],
+    ),
+    (
+        "Business",
+        &[
+            "Careers",
+            "Entrepreneurship",
+            "Investing",
+            "Management",
+            "Marketing",
+            "Non-Profit",
+        ],
+    ),
+    ("Comedy", &["Comedy Interviews", "Improv", "Stand-Up"]),
+    (
+        "Education",
+        &["Courses", "How To", "Language Learning", "Self-Improvement"],
+    ),
+    ("Fiction", &["Comedy Fiction", "Drama", "Science Fiction"]),
+    ("Government", &[]),
+    ("History", &[]),
+    (
+        "Health & Fitness",
+        &[
+            "Alternative Health",
+            "Fitness",
+            "Medicine",
+            "Mental Health",
+            "Nutrition",
+            "Sexuality",
+        ],
+    ),
+    (
+        "Kids & Family",
+        &[
+            "Education for Kids",
+            "Parenting",
+            "Pets & Animals",
+            "Stories for Kids",
+        ],
+    ),
+    (
+        "Leisure",
+        &[
+            "Animation & Manga",
+            "Automotive",
+            "Aviation",
+            "Crafts",
+            "Games",
+            "Hobbies",
+            "Home & Garden",
+            "Video Games",
+        ],
+    ),
+    (
+        "Music",
+        &["Music Commentary", "Music History", "Music Interviews"],
+    ),
+    (
+        "News",
+        &[
+            "Business News",
+            "Daily News",
+            "Entertainment News",
+            "News Commentary",
+            "Politics",
+            "Sports News",
+            "Tech News",
+        ],
+    ),
+    (
+        "Religion & Spirituality",
+        &[
+            "Buddhism",
+            "Christianity",
+            "Hinduism",
+            "Islam",
+            "Judaism",
+            "Religion",
+            "Spirituality",
+        ],
+    ),
+    (
+        "Science",
+        &[
+            "Astronomy",
+            "Chemistry",
+            "Earth Sciences",
+            "Life Sciences",
+            "Mathematics",
+            "Natural Sciences",
+            "Nature",
+            "Physics",
+            "Social Sciences",
+        ],
+    ),
+    (
+        "Society & Culture",
+        &[
+            "Documentary",
+            "Personal Journals",
+            "Philosophy",
+            "Places & Travel",
+            "Relationships",
+        ],
+    ),
+    (
+        "Sports",
+        &[
+            "Baseball",
+            "Basketball",
+            "Cricket",
+            "Fantasy Sports",
+            "Football",
+            "Golf",
+            "Hockey",
+            "Rugby",
+            "Running",
+            "Soccer",
+            "Swimming",
+            "Tennis",
+            "Volleyball",
+            "Wilderness",
+            "Wrestling",
+        ],
+    ),
+    ("Technology", &[]),
+    ("True Crime", &[]),
+    (
+        "TV & Film",
+        &[
+            "After Shows",
+            "Film History",
+            "Film Interviews",
+            "Film Reviews",
+            "TV Reviews",
+        ],
+    ),
+];
+
+pub(super) fn analyze_itunes_image(image: &badpod::itunes::Image) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match &image.href {
+        Some(badpod::Url::Ok(url)) => {
+            if url.scheme() != "http" && url.scheme() != "https" {
+                errors.push(Error::InvalidAttributeWithReason(
+                    "href".to_string(),
+                    url.to_string(),
+                    "Images must be served over `http` or `https`.".to_string(),
+                ));
+            }
+            let path = url.path().to_lowercase();
+            if ![".jpg", ".jpeg", ".png"]
+                .iter()
+                .any(|ext| path.ends_with(ext))
+            {
+                errors.push(Error::Custom(
+                    Rule::ItunesImageFormat,
+                    "Apple Podcasts requires artwork in JPEG or PNG format, and the URL does not end with <code>.jpg</code> or <code>.png</code>.".to_string(),
+                ));
+            }
+            attributes.push(("href".to_string(), Value::Url(url.to_string())));
+        }
+        Some(badpod::Url::Other((s, reason))) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                "href".to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        None => errors.push(Error::MissingAttribute("href".to_string())),
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "image".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+pub(super) fn analyze_itunes_category(category: &badpod::itunes::Category) -> Node {
+    // badpod checks the names too, but only the raw text allows to suggest the right ones.
+    let text = category.text.as_ref().map(ToString::to_string);
+    let mut node = analyze_category(text.as_deref(), None);
+
+    for subcategory in &category.subcategory {
+        let subcategory_text = subcategory.text.as_ref().map(ToString::to_string);
+        node.children.push(analyze_category(
+            subcategory_text.as_deref(),
+            text.as_deref(),
+        ));
+    }
+    if category.subcategory.len() > 1 {
+        node.errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "category".to_string(),
+        )));
+    }
+
+    node
+}
+
+fn analyze_category(text: Option<&str>, parent: Option<&str>) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    let allowed: Vec<&str> = match parent {
+        None => CATEGORIES.iter().map(|(name, _)| *name).collect(),
+        Some(parent) => CATEGORIES
+            .iter()
+            .find(|(name, _)| *name == parent)
+            .map(|(_, subcategories)| subcategories.to_vec())
+            .unwrap_or_default(),
+    };
+
+    match text {
+        Some(text) if allowed.contains(&text) => {
+            attributes.push(("text".to_string(), Value::Text(text.to_string())));
+        }
+        Some(text) => {
+            let reason = match allowed.iter().find(|x| x.eq_ignore_ascii_case(text)) {
+                Some(x) => format!("Categories are case-sensitive, did you mean “{x}”?"),
+                None => match parent {
+                    Some(parent) if allowed.is_empty() => {
+                        format!("Category “{parent}” has no subcategories.")
+                    }
+                    Some(parent) => format!(
+                        "Subcategories of “{parent}” are {}.",
+                        allowed
+                            .iter()
+                            .map(|x| format!("“{x}”"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    None => "Not one of [Apple Podcasts categories](https://podcasters.apple.com/support/1691-apple-podcasts-categories).".to_string(),
+                },
+            };
+            errors.push(Error::InvalidAttributeWithReason(
+                "text".to_string(),
+                text.to_string(),
+                reason,
+            ));
+        }
+        None => errors.push(Error::MissingAttribute("text".to_string())),
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "category".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+pub(super) fn analyze_itunes_explicit(explicit: &badpod::Bool) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match explicit {
+        badpod::Bool::Ok(b) => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(b.to_string())));
+        }
+        badpod::Bool::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "explicit".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+pub(super) fn analyze_itunes_duration(duration: &badpod::itunes::Duration) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match duration {
+        badpod::itunes::Duration::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        badpod::itunes::Duration::Duration(duration) => {
+            let seconds = duration.num_seconds();
+            let value = format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            );
+            attributes.push((NODE_VALUE.to_string(), Value::Object(value)));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "duration".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+pub(super) fn analyze_itunes_owner(owner: &badpod::itunes::Owner) -> Node {
+    let mut errors = Vec::new();
+    let mut children = Vec::new();
+
+    for name in &owner.name {
+        children.push(Node {
+            name: TagName(Some(Namespace::Itunes), "name".to_string()),
+            attributes: vec![(NODE_VALUE.to_string(), Value::Text(name.to_string()))],
+            ..Default::default()
+        });
+    }
+    match owner.name.len() {
+        0 => errors.push(Error::MissingChild(TagName(
+            Some(Namespace::Itunes),
+            "name".to_string(),
+        ))),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "name".to_string(),
+        ))),
+    }
+
+    for email in &owner.email {
+        let mut email_errors = Vec::new();
+        if !is_email(email) {
+            email_errors.push(Error::InvalidAttribute(
+                NODE_VALUE.to_string(),
+                email.to_string(),
+            ));
+        }
+        children.push(Node {
+            name: TagName(Some(Namespace::Itunes), "email".to_string()),
+            attributes: vec![(NODE_VALUE.to_string(), Value::Text(email.to_string()))],
+            errors: email_errors,
+            ..Default::default()
+        });
+    }
+    match owner.email.len() {
+        0 => errors.push(Error::Custom(
+            Rule::ItunesOwnerMissingEmail,
+            "Missing child <code>&lt;itunes:email&gt;</code>. Apple Podcasts and other directories use it to verify ownership of the show.".to_string(),
+        )),
+        1 => {}
+        _ => errors.push(Error::MultipleChildren(TagName(
+            Some(Namespace::Itunes),
+            "email".to_string(),
+        ))),
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "owner".to_string()),
+        errors,
+        children,
+        ..Default::default()
+    }
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !domain.contains('@')
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+pub(super) fn analyze_itunes_type(type_: &badpod::itunes::PodcastType) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match type_ {
+        badpod::itunes::PodcastType::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        _ => {
+            attributes.push((NODE_VALUE.to_string(), Value::Object(type_.to_string())));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "type".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+pub(super) fn analyze_itunes_episode_type(episode_type: &badpod::itunes::EpisodeType) -> Node {
+    let mut errors = Vec::new();
+    let mut attributes = Vec::new();
+
+    match episode_type {
+        badpod::itunes::EpisodeType::Other((s, reason)) => {
+            errors.push(Error::InvalidAttributeWithReason(
+                NODE_VALUE.to_string(),
+                s.to_string(),
+                reason.to_string(),
+            ));
+        }
+        _ => {
+            attributes.push((
+                NODE_VALUE.to_string(),
+                Value::Object(episode_type.to_string()),
+            ));
+        }
+    }
+
+    Node {
+        name: TagName(Some(Namespace::Itunes), "episodeType".to_string()),
+        errors,
+        attributes,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::validate_str;
+    use super::*;
+
+    fn children<'a>(node: &'a Node, name: &str) -> Vec<&'a Node> {
+        node.children
+            .iter()
+            .filter(|n| n.name.to_string() == name)
+            .collect()
+    }
+
+    fn value(node: &Node) -> Option<&Value> {
+        node.attributes
+            .iter()
+            .find(|(name, _)| name == NODE_VALUE)
+            .map(|(_, value)| value)
+    }
+
+    #[test]
+    fn test_analyze() {
+        let report = validate_str(
+            r#"<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+                <channel>
+                    <title>Show</title>
+                    <itunes:category text="Society &amp; Culture">
+                        <itunes:category text="Documentary"/>
+                    </itunes:category>
+                    <itunes:category text="Science">
+                        <itunes:category text="physics"/>
+                        <itunes:category text="Books"/>
+                    </itunes:category>
+                    <itunes:category text="History">
+                        <itunes:category text="Wars"/>
+                    </itunes:category>
+                    <itunes:category text="Cooking"/>
+                    <itunes:owner>
+                        <itunes:name>Jane</itunes:name>
+                        <itunes:name>John</itunes:name>
+                        <itunes:email>jane.example.com</itunes:email>
+                    </itunes:owner>
+                    <item>
+                        <title>Episode</title>
+                        <itunes:duration>3723</itunes:duration>
+                        <itunes:episodeType>trailer</itunes:episodeType>
+                    </item>
+                </channel>
+            </rss>"#,
+        )
+        .unwrap();
+        let channel = children(&report.root, "channel")[0];
+        let categories = children(channel, "itunes:category");
+        let text_reason = |node: &Node| match &node.errors[..] {
+            [Error::InvalidAttributeWithReason(attr, _, reason)] if attr == "text" => {
+                reason.to_string()
+            }
+            errors => panic!("unexpected findings {errors:?}"),
+        };
+
+        assert!(categories[0].errors.is_empty());
+        assert!(categories[0].children[0].errors.is_empty());
+
+        assert_eq!(
+            categories[1].errors,
+            [Error::MultipleChildren(TagName(
+                Some(Namespace::Itunes),
+                "category".to_string()
+            ))]
+        );
+        assert_eq!(
+            text_reason(&categories[1].children[0]),
+            "Categories are case-sensitive, did you mean “Physics”?"
+        );
+        assert!(
+            text_reason(&categories[1].children[1]).starts_with("Subcategories of “Science” are")
+        );
+        assert_eq!(
+            text_reason(&categories[2].children[0]),
+            "Category “History” has no subcategories."
+        );
+        assert!(text_reason(categories[3]).starts_with("Not one of"));
+
+        let owner = children(channel, "itunes:owner")[0];
+        assert_eq!(
+            owner.errors,
+            [Error::MultipleChildren(TagName(
+                Some(Namespace::Itunes),
+                "name".to_string()
+            ))]
+        );
+        let email = children(owner, "itunes:email")[0];
+        assert_eq!(
+            email.errors,
+            [Error::InvalidAttribute(
+                NODE_VALUE.to_string(),
+                "jane.example.com".to_string()
+            )]
+        );
+
+        let item = children(channel, "item")[0];
+        let duration = children(item, "itunes:duration")[0];
+        assert_eq!(value(duration), Some(&Value::Object("1:02:03".to_string())));
+        let episode_type = children(item, "itunes:episodeType")[0];
+        assert_eq!(
+            value(episode_type),
+            Some(&Value::Object("trailer".to_string()))
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! [SPDX](https://spdx.org/licenses/) identifiers in `podcast:license`.
+
+use super::{escape_html, Error, Rule, Value};
+
+/// Identifier as it is written in the SPDX license list.
+///
+/// badpod lowercases the identifiers that it recognizes, but the SPDX license list does not.
+pub(super) fn spdx_name(name: &str) -> String {
+    spdx::identifiers::LICENSES
+        .iter()
+        .find(|(id, _, _)| id.eq_ignore_ascii_case(name))
+        .map_or_else(|| name.to_string(), |(id, _, _)| id.to_string())
+}
+
+/// Checks the license against the SPDX license list.
+///
+/// Only licenses that are not on the list need a `url`.
+pub(super) fn analyze_spdx(
+    name: &str,
+    has_url: bool,
+    errors: &mut Vec<Error>,
+    attributes: &mut Vec<(String, Value)>,
+) {
+    let escaped = escape_html(name);
+    match spdx::license_id(name) {
+        Some(id) => {
+            attributes.push((
+                "full name".to_string(),
+                Value::Text(id.full_name.to_string()),
+            ));
+            attributes.push(("status".to_string(), Value::Object(status(id).to_string())));
+            if id.is_deprecated() {
+                let replacements = replacements(name, id)
+                    .iter()
+                    .map(|x| format!("“{x}”"))
+                    .collect::<Vec<_>>();
+                let hint = if replacements.is_empty() {
+                    String::new()
+                } else {
+                    format!(" Use {} instead.", replacements.join(" or "))
+                };
+                errors.push(Error::Custom(
+                    Rule::DeprecatedLicense,
+                    format!("“{escaped}” is a deprecated SPDX license identifier.{hint}"),
+                ));
+            }
+        }
+        None => {
+            if !has_url {
+                errors.push(Error::MissingAttribute("url".to_string()));
+            }
+            let message = match suggestion(name) {
+                Some(x) => {
+                    format!("“{escaped}” is not an SPDX license identifier, did you mean “{x}”?")
+                }
+                None => format!(
+                    "“{escaped}” is not an SPDX license identifier, so apps can only link to its URL."
+                ),
+            };
+            errors.push(Error::Custom(Rule::UnknownLicense, message));
+        }
+    }
+}
+
+fn status(id: spdx::LicenseId) -> &'static str {
+    match (id.is_osi_approved(), id.is_fsf_free_libre()) {
+        (true, true) => "OSI approved, FSF free/libre",
+        (true, false) => "OSI approved",
+        (false, true) => "FSF free/libre",
+        (false, false) => "neither OSI approved nor FSF free/libre",
+    }
+}
+
+/// Identifiers that replaced a deprecated one, e.g. “GPL-2.0-only” for “GPL-2.0”.
+fn replacements(name: &str, id: spdx::LicenseId) -> Vec<&'static str> {
+    let suffixes: &[&str] = if name.ends_with('+') {
+        &["-or-later"]
+    } else {
+        &["-only", "-or-later"]
+    };
+    suffixes
+        .iter()
+        .filter_map(|suffix| spdx::license_id(&format!("{}{suffix}", id.name)))
+        .filter(|x| !x.is_deprecated())
+        .map(|x| x.name)
+        .collect()
+}
+
+/// Finds the identifier that an unknown license name most likely stands for.
+fn suggestion(name: &str) -> Option<&'static str> {
+    let id = spdx::identifiers::LICENSES
+        .iter()
+        .find(|(id, _, _)| id.eq_ignore_ascii_case(name))
+        .and_then(|(id, _, _)| spdx::license_id(id))
+        .or_else(|| match spdx::imprecise_license_id(name) {
+            Some((id, len)) if len == name.len() => Some(id),
+            _ => None,
+        })?;
+    if id.is_deprecated() {
+        replacements(name, id).first().copied()
+    } else {
+        Some(id.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::validate_str;
+    use super::*;
+
+    fn findings(name: &str, has_url: bool) -> Vec<String> {
+        let mut errors = Vec::new();
+        analyze_spdx(name, has_url, &mut errors, &mut Vec::new());
+        errors.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_analyze_spdx() {
+        assert!(findings("CC-BY-4.0", false).is_empty());
+        assert_eq!(
+            findings("GPL-2.0", false),
+            ["“GPL-2.0” is a deprecated SPDX license identifier. Use “GPL-2.0-only” or “GPL-2.0-or-later” instead."]
+        );
+        assert_eq!(
+            findings("cc-by-4.0", true),
+            ["“cc-by-4.0” is not an SPDX license identifier, did you mean “CC-BY-4.0”?"]
+        );
+        assert_eq!(findings("All rights reserved", false).len(), 2);
+    }
+
+    #[test]
+    fn test_validate_license() {
+        let feed = |license: &str| {
+            validate_str(&format!("<rss version=\"2.0\" xmlns:podcast=\"https://podcastindex.org/namespace/1.0\"><channel><title>Show</title><podcast:license>{license}</podcast:license></channel></rss>")).unwrap()
+        };
+        let license_errors = |report: &super::super::Report| {
+            report.root.children[0]
+                .children
+                .iter()
+                .find(|n| n.name.to_string() == "podcast:license")
+                .unwrap()
+                .errors
+                .clone()
+        };
+
+        assert_eq!(license_errors(&feed("CC-BY-4.0")), []);
+        assert_eq!(
+            license_errors(&feed("&lt;b&gt;Mine&lt;/b&gt;"))[1],
+            Error::Custom(
+                Rule::UnknownLicense,
+                "“&lt;b&gt;Mine&lt;/b&gt;” is not an SPDX license identifier, so apps can only link to its URL.".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Checks of namespace declarations and of elements that badpod does not recognize.
+//!
+//! badpod drops elements it does not know, so these checks work on the source instead of the
+//! parsed feed.
+
+use super::source::{known_prefix, Element, KNOWN_NAMESPACES};
+use super::{escape_html, Error, Node, Rule};
+use std::collections::HashMap;
+
+const PODCAST_TAGS: [&str; 32] = [
+    "alternateEnclosure",
+    "block",
+    "chapters",
+    "chat",
+    "contentLink",
+    "episode",
+    "funding",
+    "guid",
+    "image",
+    "images",
+    "integrity",
+    "license",
+    "liveItem",
+    "location",
+    "locked",
+    "medium",
+    "person",
+    "podping",
+    "podroll",
+    "publisher",
+    "remoteItem",
+    "season",
+    "socialInteract",
+    "soundbite",
+    "source",
+    "trailer",
+    "transcript",
+    "txt",
+    "updateFrequency",
+    "value",
+    "valueRecipient",
+    "valueTimeSplit",
+];
+
+const ITUNES_TAGS: [&str; 22] = [
+    "applepodcastsverify",
+    "author",
+    "block",
+    "category",
+    "complete",
+    "duration",
+    "email",
+    "episode",
+    "episodeType",
+    "explicit",
+    "image",
+    "isClosedCaptioned",
+    "keywords",
+    "name",
+    "new-feed-url",
+    "order",
+    "owner",
+    "season",
+    "subtitle",
+    "summary",
+    "title",
+    "type",
+];
+
+/// Checks the declarations of the namespaces and reports unknown elements in them.
+///
+/// Findings about declarations are attached to the root node, and unknown elements to the node
+/// of their closest analyzed ancestor.
+pub(super) fn analyze(root: &mut Node, element: &Element) {
+    let mut elements = Vec::new();
+    flatten(element, &mut elements);
+
+    let mut declarations: Vec<(&str, &str)> = Vec::new();
+    for e in &elements {
+        for attr in &e.attributes {
+            if let Some(prefix) = attr.name.strip_prefix("xmlns:") {
+                if !declarations.contains(&(prefix, attr.value.as_str())) {
+                    declarations.push((prefix, &attr.value));
+                }
+            }
+        }
+    }
+    for (prefix, uri) in declarations {
+        if known_prefix(uri).is_some() {
+            continue;
+        }
+        if let Some(expected) = expected_uri(prefix, uri) {
+            let count = elements
+                .iter()
+                .filter(|e| e.namespace.as_deref() == Some(uri))
+                .count();
+            root.errors.push(Error::Custom(
+                Rule::IncorrectNamespaceUri,
+                format!(
+                    "Prefix <code>{}</code> is declared as “<code>{}</code>” instead of “<code>{expected}</code>”, so apps do not recognize the {}.",
+                    escape_html(prefix),
+                    escape_html(uri),
+                    elements_str(count)
+                ),
+            ));
+        }
+    }
+
+    let mut undeclared: Vec<(&str, usize)> = Vec::new();
+    for e in &elements {
+        if let (Some((prefix, _)), None) = (e.name.split_once(':'), &e.namespace) {
+            if prefix == "xml" {
+                continue;
+            }
+            match undeclared.iter_mut().find(|(p, _)| *p == prefix) {
+                Some((_, count)) => *count += 1,
+                None => undeclared.push((prefix, 1)),
+            }
+        }
+    }
+    for (prefix, count) in undeclared {
+        let hint = match KNOWN_NAMESPACES.iter().find(|(p, _)| *p == prefix) {
+            Some((_, uri)) => {
+                format!(" Add <code>xmlns:{prefix}=\"{uri}\"</code> to <code>&lt;rss&gt;</code>.")
+            }
+            None => String::new(),
+        };
+        root.errors.push(Error::Custom(
+            Rule::UndeclaredNamespace,
+            format!(
+                "Prefix <code>{}</code> is used by {} but never declared.{hint}",
+                escape_html(prefix),
+                elements_str(count)
+            ),
+        ));
+    }
+
+    analyze_tags(root, element);
+}
+
+fn flatten<'a>(element: &'a Element, elements: &mut Vec<&'a Element>) {
+    elements.push(element);
+    for child in &element.children {
+        flatten(child, elements);
+    }
+}
+
+fn elements_str(count: usize) -> String {
+    match count {
+        1 => "1 element".to_string(),
+        n => format!("{n} elements"),
+    }
+}
+
+/// Guesses which known namespace an unknown URI was meant to be.
+fn expected_uri(prefix: &str, uri: &str) -> Option<&'static str> {
+    let uri = uri.to_lowercase();
+    let intended = if uri.contains("itunes.com") {
+        "itunes"
+    } else if prefix == "podcast"
+        || uri.contains("podcastindex")
+        || uri.contains("podcast-namespace")
+    {
+        "podcast"
+    } else if prefix == "itunes" {
+        "itunes"
+    } else {
+        return None;
+    };
+    KNOWN_NAMESPACES
+        .iter()
+        .find(|(p, _)| *p == intended)
+        .map(|(_, uri)| *uri)
+}
+
+/// Matches elements with nodes the same way as [`super::source::attach_spans`].
+fn analyze_tags(node: &mut Node, element: &Element) {
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, child_node) in node.children.iter().enumerate() {
+        by_name
+            .entry(child_node.name.to_string())
+            .or_default()
+            .push(i);
+    }
+    let mut by_name: HashMap<String, std::vec::IntoIter<usize>> = by_name
+        .into_iter()
+        .map(|(name, indices)| (name, indices.into_iter()))
+        .collect();
+
+    for child in &element.children {
+        if let Some(error) = unknown_tag(child) {
+            node.errors.push(error);
+            continue;
+        }
+
+        match by_name.get_mut(&child.name).and_then(Iterator::next) {
+            Some(i) => analyze_tags(&mut node.children[i], child),
+            None => analyze_tags(node, child),
+        }
+    }
+}
+
+fn unknown_tag(element: &Element) -> Option<Error> {
+    let (prefix, local_name) = element.name.split_once(':')?;
+    let namespace = element.namespace.as_deref()?;
+    let (known_tags, namespace_name): (&[&str], &str) = match known_prefix(namespace)? {
+        "podcast" => (&PODCAST_TAGS, "podcast"),
+        "itunes" => (&ITUNES_TAGS, "iTunes"),
+        _ => return None,
+    };
+    if known_tags.contains(&local_name) {
+        return None;
+    }
+
+    let suggestion = match closest(local_name, known_tags) {
+        Some(tag) => format!(
+            " Did you mean <code>&lt;{}:{tag}&gt;</code>?",
+            escape_html(prefix)
+        ),
+        None => String::new(),
+    };
+    Some(Error::Custom(
+        Rule::UnknownTag,
+        format!(
+            "<code>&lt;{}&gt;</code> is not defined in the {namespace_name} namespace, so apps ignore it.{suggestion}",
+            escape_html(&element.name)
+        ),
+    ))
+}
+
+/// Returns the known name closest to `name`, if it is close enough to be a typo.
+pub(super) fn closest<'a>(name: &str, known: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_lowercase();
+    known
+        .iter()
+        .map(|tag| (edit_distance(&name, &tag.to_lowercase()), *tag))
+        .filter(|(distance, _)| *distance <= 2 && *distance < name.chars().count())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, tag)| tag)
+}
+
+/// Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::source;
+    use super::super::{Namespace, TagName};
+    use super::*;
+
+    #[test]
+    fn test_closest() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(closest("transcripts", &PODCAST_TAGS), Some("transcript"));
+        assert_eq!(closest("Funding", &PODCAST_TAGS), Some("funding"));
+        assert_eq!(closest("new-feed-uri", &ITUNES_TAGS), Some("new-feed-url"));
+        assert_eq!(closest("something", &PODCAST_TAGS), None);
+    }
+
+    #[test]
+    fn test_tags_parsed_by_badpod() {
+        let itunes = [
+            "author",
+            "block",
+            "category",
+            "complete",
+            "duration",
+            "email",
+            "episode",
+            "episodeType",
+            "explicit",
+            "image",
+            "name",
+            "new-feed-url",
+            "owner",
+            "season",
+            "title",
+            "type",
+        ];
+        let podcast = [
+            "alternateEnclosure",
+            "block",
+            "chapters",
+            "contentLink",
+            "episode",
+            "funding",
+            "guid",
+            "images",
+            "integrity",
+            "license",
+            "liveItem",
+            "location",
+            "locked",
+            "medium",
+            "person",
+            "season",
+            "socialInteract",
+            "soundbite",
+            "source",
+            "trailer",
+            "transcript",
+            "txt",
+            "value",
+            "valueRecipient",
+        ];
+        let tags: String = itunes
+            .iter()
+            .map(|tag| format!("<itunes:{tag}/>"))
+            .chain(podcast.iter().map(|tag| format!("<podcast:{tag}/>")))
+            .collect();
+        let xml = format!("<rss xmlns:podcast=\"https://podcastindex.org/namespace/1.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\"><channel>{tags}</channel></rss>");
+        let mut root = Node {
+            name: TagName(None, "rss".to_string()),
+            ..Default::default()
+        };
+        analyze(&mut root, &source::parse(&xml).unwrap());
+
+        assert_eq!(root.errors, []);
+    }
+
+    #[test]
+    fn test_analyze_escapes_uri() {
+        let xml = "<rss xmlns:itunes='http://www.itunes.com/\">'><channel/></rss>";
+        let mut root = Node {
+            name: TagName(None, "rss".to_string()),
+            ..Default::default()
+        };
+        analyze(&mut root, &source::parse(xml).unwrap());
+
+        match &root.errors[..] {
+            [Error::Custom(Rule::IncorrectNamespaceUri, message)] => {
+                assert!(message.contains("“<code>http://www.itunes.com/&quot;&gt;</code>”"))
+            }
+            errors => panic!("unexpected findings {errors:?}"),
+        }
+    }
+
+    #[test]
+    fn test_analyze() {
+        let xml = "<rss xmlns:podcast=\"https://podcastindex.org/namespace/1.0/\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\"><channel><podcast:locked>yes</podcast:locked><itunes:explict>no</itunes:explict><googleplay:author>A</googleplay:author><item><itunes:duration>1</itunes:duration></item></channel></rss>";
+        let element = source::parse(xml).unwrap();
+        let mut root = Node {
+            name: TagName(None, "rss".to_string()),
+            children: vec![Node {
+                name: TagName(None, "channel".to_string()),
+                children: vec![Node {
+                    name: TagName(None, "item".to_string()),
+                    children: vec![Node {
+                        name: TagName(Some(Namespace::Itunes), "duration".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        analyze(&mut root, &element);
+
+        let rules = |node: &Node| {
+            node.errors
+                .iter()
+                .map(|error| match error {
+                    Error::Custom(rule, _) => *rule,
+                    _ => panic!("unexpected finding {error:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rules(&root),
+            [Rule::IncorrectNamespaceUri, Rule::UndeclaredNamespace]
+        );
+        assert_eq!(rules(&root.children[0]), [Rule::UnknownTag]);
+        assert!(root.children[0].errors[0]
+            .to_string()
+            .contains("Did you mean <itunes:explicit>?"));
+        assert!(root.children[0].children[0].errors.is_empty());
+    }
+}
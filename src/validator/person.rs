@@ -0,0 +1,245 @@
+//! Groups and roles of `podcast:person` from the
+//! [podcast taxonomy](https://github.com/Podcastindex-org/podcast-namespace/blob/main/taxonomy.json).
+
+use super::namespaces::closest;
+use super::{Error, Rule, Value};
+use badpod::podcast::{PersonGroup, PersonRole};
+
+/// Assumed when `group` is omitted.
+const DEFAULT_GROUP: &str = "cast";
+/// Assumed when `role` is omitted.
+const DEFAULT_ROLE: &str = "host";
+
+/// Groups and their roles, in lowercase.
+pub const TAXONOMY: [(&str, &[&str]); 11] = [
+    (
+        "creative direction",
+        &[
+            "director",
+            "assistant director",
+            "executive producer",
+            "senior producer",
+            "producer",
+            "associate producer",
+            "development producer",
+            "creative director",
+        ],
+    ),
+    (
+        "cast",
+        &[
+            "host",
+            "co-host",
+            "guest host",
+            "guest",
+            "voice actor",
+            "narrator",
+            "announcer",
+            "reporter",
+        ],
+    ),
+    (
+        "writing",
+        &[
+            "author",
+            "editorial director",
+            "co-writer",
+            "writer",
+            "songwriter",
+            "guest writer",
+            "story editor",
+            "managing editor",
+            "script editor",
+            "script coordinator",
+            "researcher",
+            "editor",
+            "fact checker",
+            "translator",
+            "transcriber",
+            "logger",
+        ],
+    ),
+    (
+        "audio production",
+        &[
+            "studio coordinator",
+            "technical director",
+            "technical manager",
+            "audio engineer",
+            "remote recording engineer",
+            "post production engineer",
+        ],
+    ),
+    (
+        "audio post-production",
+        &[
+            "audio editor",
+            "sound designer",
+            "foley artist",
+            "composer",
+            "theme music",
+            "music production",
+            "music contributor",
+        ],
+    ),
+    (
+        "administration",
+        &[
+            "production coordinator",
+            "booking coordinator",
+            "production assistant",
+            "content manager",
+            "marketing manager",
+            "sales representative",
+            "sales manager",
+        ],
+    ),
+    ("visuals", &["graphic designer", "cover art designer"]),
+    ("community", &["social media manager"]),
+    ("misc.", &["consultant", "intern"]),
+    (
+        "video production",
+        &[
+            "camera operator",
+            "lighting designer",
+            "camera grip",
+            "assistant camera",
+        ],
+    ),
+    ("video post-production", &["editor", "assistant editor"]),
+];
+
+/// Checks `group` and `role`, and that the role belongs to the group.
+///
+/// Omitted attributes are shown with their default values, because the role is checked against
+/// the default group too.
+pub(super) fn analyze_group_and_role(
+    group: Option<&PersonGroup>,
+    role: Option<&PersonRole>,
+    errors: &mut Vec<Error>,
+    attributes: &mut Vec<(String, Value)>,
+) {
+    let group = match group {
+        Some(PersonGroup::Other((s, reason))) => {
+            let groups: Vec<&str> = TAXONOMY.iter().map(|(name, _)| *name).collect();
+            errors.push(Error::InvalidAttributeWithReason(
+                "group".to_string(),
+                s.to_string(),
+                suggestion(s, &groups).unwrap_or_else(|| reason.to_string()),
+            ));
+            None
+        }
+        Some(group) => {
+            attributes.push(("group".to_string(), Value::Object(group.to_string())));
+            find_group(&group.to_string())
+        }
+        None => {
+            attributes.push((
+                "group".to_string(),
+                Value::Object(format!("{DEFAULT_GROUP} (default)")),
+            ));
+            find_group(DEFAULT_GROUP)
+        }
+    };
+
+    let role = match role {
+        Some(PersonRole::Other((s, reason))) => {
+            // Near-misses are more likely meant to be a role of the declared group.
+            let in_group = match group {
+                Some((_, roles)) => suggestion(s, roles),
+                None => None,
+            };
+            let all_roles: Vec<&str> = TAXONOMY
+                .iter()
+                .flat_map(|(_, roles)| roles.iter().copied())
+                .collect();
+            errors.push(Error::InvalidAttributeWithReason(
+                "role".to_string(),
+                s.to_string(),
+                in_group
+                    .or_else(|| suggestion(s, &all_roles))
+                    .unwrap_or_else(|| reason.to_string()),
+            ));
+            return;
+        }
+        Some(role) => {
+            attributes.push(("role".to_string(), Value::Object(role.to_string())));
+            role.to_string().to_lowercase()
+        }
+        None => {
+            attributes.push((
+                "role".to_string(),
+                Value::Object(format!("{DEFAULT_ROLE} (default)")),
+            ));
+            DEFAULT_ROLE.to_string()
+        }
+    };
+
+    let groups = groups_of(&role);
+    if let Some((group, roles)) = group {
+        // Roles missing from the taxonomy above are left to badpod.
+        if !roles.contains(&role.as_str()) && !groups.is_empty() {
+            let groups = groups
+                .iter()
+                .map(|x| format!("“{x}”"))
+                .collect::<Vec<_>>()
+                .join(" or ");
+            errors.push(Error::Custom(
+                Rule::PersonRoleNotInGroup,
+                format!(
+                    "Role “{role}” does not belong to group “{group}”. It belongs to group {groups}."
+                ),
+            ));
+        }
+    }
+}
+
+fn find_group(name: &str) -> Option<(&'static str, &'static [&'static str])> {
+    TAXONOMY
+        .iter()
+        .find(|(group, _)| group.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+fn groups_of(role: &str) -> Vec<&'static str> {
+    TAXONOMY
+        .iter()
+        .filter(|(_, roles)| roles.contains(&role))
+        .map(|(group, _)| *group)
+        .collect()
+}
+
+/// Suggests the closest value, ignoring case, spacing and plurals.
+fn suggestion(value: &str, allowed: &[&str]) -> Option<String> {
+    let normalized = value
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let normalized = normalized.strip_suffix('s').unwrap_or(&normalized);
+    let allowed_spaced: Vec<String> = allowed.iter().map(|x| x.replace('-', " ")).collect();
+    let allowed_spaced: Vec<&str> = allowed_spaced.iter().map(|x| x.as_str()).collect();
+    let closest = closest(normalized, &allowed_spaced)?;
+    let index = allowed_spaced.iter().position(|x| *x == closest)?;
+    Some(format!("Did you mean “{}”?", allowed[index]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggestion() {
+        let roles = TAXONOMY[1].1;
+        assert_eq!(
+            suggestion("Co Hosts", roles),
+            Some("Did you mean “co-host”?".to_string())
+        );
+        assert_eq!(
+            suggestion("guests", roles),
+            Some("Did you mean “guest”?".to_string())
+        );
+        assert_eq!(suggestion("plumber", roles), None);
+        assert_eq!(groups_of("editor"), ["writing", "video post-production"]);
+    }
+}
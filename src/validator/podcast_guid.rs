@@ -0,0 +1,271 @@
+//! Derivation of `<podcast:guid>` from the feed URL.
+//!
+//! See <https://podcastindex.org/namespace/1.0#guid>.
+
+use super::source::Element;
+use super::{
+    decode_entities, escape_html, Error, Namespace, Node, Rule, TagName, Value, NODE_VALUE,
+};
+use uuid::Uuid;
+
+/// Namespace of the UUIDv5 used for podcast GUIDs.
+pub const NAMESPACE_PODCAST: Uuid = Uuid::from_bytes([
+    0xea, 0xd4, 0xc2, 0x36, 0xbf, 0x58, 0x58, 0xc6, 0xa2, 0xc6, 0xa6, 0xb2, 0x8d, 0x12, 0x8c, 0xb6,
+]);
+
+/// Hashes the given string as is.
+pub fn hash(s: &str) -> String {
+    Uuid::new_v5(&NAMESPACE_PODCAST, s.as_bytes()).to_string()
+}
+
+/// Removes the protocol scheme and trailing slashes, as the specification requires before hashing.
+pub fn normalize(url: &str) -> &str {
+    let url = url.trim();
+    let url = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => url,
+    };
+    url.trim_end_matches('/')
+}
+
+/// Derives the GUID of a podcast from its feed URL.
+pub fn from_feed_url(url: &str) -> String {
+    hash(normalize(url))
+}
+
+/// Compares the declared `<podcast:guid>` with the GUIDs derived from the URLs known for the feed:
+/// the one it was fetched from, `<itunes:new-feed-url>` and the Atom self link.
+pub(super) fn analyze(
+    root: &mut Node,
+    element: &Element,
+    source: &str,
+    fetched_from: Option<&str>,
+) {
+    let channel_element = match element.children.iter().find(|e| e.name == "channel") {
+        Some(x) => x,
+        None => return,
+    };
+
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    if let Some(url) = fetched_from {
+        candidates.push((
+            "the URL the feed was fetched from".to_string(),
+            url.to_string(),
+        ));
+    }
+    let declared_urls = declared_urls(channel_element, source);
+    if let Some(url) = declared_urls.new_feed_url {
+        candidates.push(("<code>&lt;itunes:new-feed-url&gt;</code>".to_string(), url));
+    }
+    if let Some(url) = declared_urls.self_link {
+        candidates.push(("the Atom self link".to_string(), url));
+    }
+    candidates.retain(|(_, url)| !normalize(url).is_empty());
+    if candidates.is_empty() {
+        return;
+    }
+
+    let guid_node = root
+        .children
+        .iter_mut()
+        .find(|n| n.name == TagName(None, "channel".to_string()))
+        .and_then(|channel| {
+            channel
+                .children
+                .iter_mut()
+                .find(|n| n.name == TagName(Some(Namespace::Podcast), "guid".to_string()))
+        });
+    let guid_node = match guid_node {
+        Some(x) => x,
+        None => return,
+    };
+    let declared = match guid_node
+        .attributes
+        .iter()
+        .find(|(name, _)| name == NODE_VALUE)
+    {
+        Some((_, Value::Text(guid))) => guid.to_lowercase(),
+        _ => return,
+    };
+
+    if candidates
+        .iter()
+        .any(|(_, url)| from_feed_url(url) == declared)
+    {
+        return;
+    }
+
+    for (source_name, url) in &candidates {
+        if let Some((hashed, mistake)) = mistakes(url).find(|(hashed, _)| hash(hashed) == declared)
+        {
+            guid_node.errors.push(Error::Custom(
+                Rule::PodcastGuidHashingMistake,
+                format!(
+                    "GUID does not match {source_name} “<code>{}</code>”, which should give “<code>{}</code>”. The declared GUID was derived from “<code>{}</code>”, so {mistake}.",
+                    escape_html(normalize(url)),
+                    from_feed_url(url),
+                    escape_html(&hashed),
+                ),
+            ));
+            return;
+        }
+    }
+
+    let (source_name, url) = &candidates[0];
+    guid_node.errors.push(Error::Custom(
+        Rule::PodcastGuidMismatch,
+        format!(
+            "GUID does not match {source_name} “<code>{}</code>”, which would give “<code>{}</code>”. This is expected if the feed has moved since the GUID was generated, because the GUID must not change when it does.",
+            escape_html(normalize(url)),
+            from_feed_url(url),
+        ),
+    ));
+}
+
+/// URLs that a feed declares for itself.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub(super) struct DeclaredUrls {
+    /// Content of `<itunes:new-feed-url>`.
+    pub new_feed_url: Option<String>,
+    /// `href` of `<atom:link rel="self">`.
+    pub self_link: Option<String>,
+}
+
+pub(super) fn declared_urls(channel_element: &Element, source: &str) -> DeclaredUrls {
+    let mut urls = DeclaredUrls::default();
+    for e in &channel_element.children {
+        if e.name == "itunes:new-feed-url" && urls.new_feed_url.is_none() {
+            if let Some(content) = e.content {
+                urls.new_feed_url =
+                    Some(decode_entities(source[content.start..content.end].trim()));
+            }
+        }
+        let attr = |name: &str| {
+            e.attributes
+                .iter()
+                .find(|a| a.name == name)
+                .map(|a| decode_entities(&a.value))
+        };
+        if e.name == "atom:link"
+            && attr("rel").as_deref() == Some("self")
+            && urls.self_link.is_none()
+        {
+            urls.self_link = attr("href");
+        }
+    }
+    urls
+}
+
+/// Strings that publishers commonly hash by mistake instead of the normalized URL.
+fn mistakes(url: &str) -> impl Iterator<Item = (String, &'static str)> {
+    let normalized = normalize(url);
+    vec![
+        (
+            format!("https://{normalized}"),
+            "the protocol scheme was probably not removed before hashing",
+        ),
+        (
+            format!("http://{normalized}"),
+            "the protocol scheme was probably not removed before hashing",
+        ),
+        (
+            format!("{normalized}/"),
+            "the trailing slash was probably not removed before hashing",
+        ),
+        (
+            format!("https://{normalized}/"),
+            "the protocol scheme and the trailing slash were probably not removed before hashing",
+        ),
+        (
+            format!("http://{normalized}/"),
+            "the protocol scheme and the trailing slash were probably not removed before hashing",
+        ),
+    ]
+    .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_feed_url() {
+        // Example from the specification.
+        assert_eq!(
+            from_feed_url("https://mp3s.nashownotes.com/pc20rss.xml"),
+            "917393e3-1b1e-5cef-ace4-edaa54e1f810"
+        );
+        assert_eq!(normalize("http://example.com/feed/"), "example.com/feed");
+        assert_eq!(normalize("example.com/feed"), "example.com/feed");
+    }
+
+    fn guid_errors(guid: &str, fetched_from: &str) -> Vec<Error> {
+        let report = super::super::validate_fetched(
+            &format!(
+                r#"<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd">
+                    <channel>
+                        <title>Show</title>
+                        <podcast:guid>{guid}</podcast:guid>
+                        <itunes:new-feed-url>https://example.com/feed.xml?a=1&amp;b=&#50;</itunes:new-feed-url>
+                    </channel>
+                </rss>"#
+            ),
+            fetched_from,
+        )
+        .unwrap();
+        let channel = &report.root.children[0];
+        channel
+            .children
+            .iter()
+            .find(|n| n.name == TagName(Some(Namespace::Podcast), "guid".to_string()))
+            .unwrap()
+            .errors
+            .clone()
+    }
+
+    fn rules(errors: &[Error]) -> Vec<Rule> {
+        errors
+            .iter()
+            .map(|error| match error {
+                Error::Custom(rule, _) => *rule,
+                _ => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        // Entities in the declared URL are decoded before hashing, including numeric ones.
+        let new_feed_url = "example.com/feed.xml?a=1&b=2";
+        assert!(guid_errors(&hash(new_feed_url), "https://example.com/old.xml").is_empty());
+        assert!(guid_errors(
+            &hash("example.com/old.xml").to_uppercase(),
+            "https://example.com/old.xml/"
+        )
+        .is_empty());
+
+        let errors = guid_errors(
+            &hash(&format!("https://{new_feed_url}")),
+            "https://example.com/old.xml",
+        );
+        assert_eq!(rules(&errors), [Rule::PodcastGuidHashingMistake]);
+        assert!(errors[0]
+            .to_string()
+            .contains("the protocol scheme was probably not removed"));
+
+        let errors = guid_errors(&hash("example.com/old.xml/"), "https://example.com/old.xml");
+        assert_eq!(rules(&errors), [Rule::PodcastGuidHashingMistake]);
+        assert!(errors[0]
+            .to_string()
+            .contains("the trailing slash was probably not removed"));
+
+        let errors = guid_errors(
+            &hash("example.com/other.xml"),
+            "https://example.com/old.xml",
+        );
+        assert_eq!(rules(&errors), [Rule::PodcastGuidMismatch]);
+        assert!(errors[0]
+            .to_string()
+            .contains(&from_feed_url("https://example.com/old.xml")));
+    }
+}
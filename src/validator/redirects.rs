@@ -0,0 +1,196 @@
+//! Redirects followed to fetch a feed, checked against the URLs that the feed declares.
+//!
+//! A feed that has moved should be redirected permanently from its old URL, and declare its new
+//! URL in `<itunes:new-feed-url>` and the Atom self link.
+
+use super::podcast_guid::{declared_urls, normalize};
+use super::{escape_html, source, Error, Rule};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Request made while following a feed URL.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Hop {
+    pub url: String,
+    /// Status of the response, unless it was hidden. Browsers hide the statuses of redirects.
+    pub status: Option<u16>,
+}
+
+/// Checks the redirects, the first hop being the requested URL and the last one the feed itself.
+pub fn analyze(hops: &[Hop], source: &str) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let (requested, feed_url) = match (hops.first(), hops.last()) {
+        (Some(first), Some(last)) => (&first.url, &last.url),
+        _ => return errors,
+    };
+    let redirects = &hops[..hops.len() - 1];
+    let quote = |url: &str| format!("“<code>{}</code>”", escape_html(url));
+
+    if redirects.len() > 1 {
+        errors.push(Error::Custom(
+            Rule::RedirectChain,
+            format!(
+                "{} goes through {} redirects before reaching the feed. Each of them slows down every refresh, so redirect straight to {}.",
+                quote(requested),
+                redirects.len(),
+                quote(feed_url)
+            ),
+        ));
+    }
+    for hop in redirects {
+        if let Some(status @ (302 | 303 | 307)) = hop.status {
+            errors.push(Error::Custom(
+                Rule::TemporaryRedirect,
+                format!(
+                    "{} redirects with status {status}, so apps treat the move as temporary and keep requesting the old URL. Use 301 or 308 if the feed has moved for good.",
+                    quote(&hop.url)
+                ),
+            ));
+        }
+    }
+
+    let root = match source::parse(source) {
+        Some(root) => root,
+        None => return errors,
+    };
+    let channel = match root.children.iter().find(|e| e.name == "channel") {
+        Some(x) => x,
+        None => return errors,
+    };
+    let declared = declared_urls(channel, source);
+    let has_moved = !redirects.is_empty() && normalize(requested) != normalize(feed_url);
+
+    match &declared.new_feed_url {
+        Some(url) if normalize(url) != normalize(feed_url) => errors.push(Error::Custom(
+            Rule::NewFeedUrlMismatch,
+            format!(
+                "<code>&lt;itunes:new-feed-url&gt;</code> is {}, but the feed is served from {}. Apps that follow it may end up at an outdated or missing feed.",
+                quote(url),
+                quote(feed_url)
+            ),
+        )),
+        Some(_) => {}
+        None if has_moved => errors.push(Error::Custom(
+            Rule::NewFeedUrlMismatch,
+            format!(
+                "The feed has moved from {} to {}, but has no <code>&lt;itunes:new-feed-url&gt;</code>. Apple Podcasts needs both the redirect and the tag to update its directory.",
+                quote(requested),
+                quote(feed_url)
+            ),
+        )),
+        None => {}
+    }
+
+    if let Some(url) = &declared.self_link {
+        if normalize(url) != normalize(feed_url) {
+            errors.push(Error::Custom(
+                Rule::SelfLinkMismatch,
+                format!(
+                    "The Atom self link is {}, but the feed is served from {}.",
+                    quote(url),
+                    quote(feed_url)
+                ),
+            ));
+        }
+    }
+
+    let host = |url: &str| {
+        Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+    };
+    if has_moved && host(requested) != host(feed_url) {
+        let locked = channel
+            .children
+            .iter()
+            .find(|e| e.name == "podcast:locked")
+            .and_then(|e| e.content)
+            .map(|content| source[content.start..content.end].trim().to_lowercase());
+        if locked.as_deref() != Some("yes") {
+            errors.push(Error::Custom(
+                Rule::MovedFeedUnlocked,
+                "The feed has moved to another host but is not locked with <code>&lt;podcast:locked&gt;yes&lt;/podcast:locked&gt;</code>, so other platforms may still import it.".to_string(),
+            ));
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(hops: &[(&str, Option<u16>)], source: &str) -> Vec<Rule> {
+        let hops: Vec<Hop> = hops
+            .iter()
+            .map(|(url, status)| Hop {
+                url: url.to_string(),
+                status: *status,
+            })
+            .collect();
+        analyze(&hops, source)
+            .into_iter()
+            .map(|error| match error {
+                Error::Custom(rule, _) => rule,
+                _ => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        let moved = "<rss><channel><itunes:new-feed-url>https://new.example.com/feed</itunes:new-feed-url><atom:link rel=\"self\" href=\"https://new.example.com/feed/\"/><podcast:locked>yes</podcast:locked></channel></rss>";
+        assert!(rules(
+            &[
+                ("https://old.example.com/feed", Some(301)),
+                ("https://new.example.com/feed", Some(200)),
+            ],
+            moved,
+        )
+        .is_empty());
+
+        assert_eq!(
+            rules(
+                &[
+                    ("http://old.example.com/feed", Some(301)),
+                    ("https://old.example.com/feed", Some(302)),
+                    ("https://new.example.com/feed", Some(200)),
+                ],
+                "<rss><channel></channel></rss>",
+            ),
+            [
+                Rule::RedirectChain,
+                Rule::TemporaryRedirect,
+                Rule::NewFeedUrlMismatch,
+                Rule::MovedFeedUnlocked,
+            ]
+        );
+
+        // Without redirects, only the declared URLs are checked.
+        assert_eq!(
+            rules(&[("https://old.example.com/feed", None)], moved),
+            [Rule::NewFeedUrlMismatch, Rule::SelfLinkMismatch]
+        );
+    }
+
+    #[test]
+    fn test_analyze_escapes_urls() {
+        let hops = [
+            Hop {
+                url: "https://example.com/<b>".to_string(),
+                status: Some(302),
+            },
+            Hop {
+                url: "https://example.com/feed".to_string(),
+                status: Some(200),
+            },
+        ];
+        match &analyze(&hops, "")[..] {
+            [Error::Custom(Rule::TemporaryRedirect, message)] => {
+                assert!(message.starts_with("“<code>https://example.com/&lt;b&gt;</code>”"))
+            }
+            errors => panic!("unexpected findings {errors:?}"),
+        }
+    }
+}
@@ -0,0 +1,520 @@
+//! Stable identifiers of the checks and user settings that silence or downgrade them.
+//!
+//! Structural findings, such as a missing attribute, are identified by the element and the
+//! attribute or child they concern, e.g. `podcast:transcript.missing-attribute.url`. Other checks
+//! have a [`Rule`] of their own.
+
+use super::{Error, Node, Report, Severity, NODE_VALUE};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+mod docs;
+pub(super) use docs::PODCAST_SPEC;
+pub use docs::{rule_doc, RuleDoc};
+
+/// Defines [`Rule`] along with [`Rule::ALL`], so that the list cannot miss a variant.
+macro_rules! rules {
+    ($($rule:ident,)*) => {
+        /// Check that is not covered by the structural findings.
+        #[derive(PartialEq, Eq, Debug, Clone, Copy)]
+        pub enum Rule {
+            $($rule,)*
+        }
+
+        impl Rule {
+            pub const ALL: [Rule; [$(Rule::$rule,)*].len()] = [$(Rule::$rule,)*];
+        }
+    };
+}
+
+rules! {
+    ItemMissingEnclosure,
+    ItemMissingGuid,
+    ItemMissingPubDate,
+    FutureDate,
+    EnclosureZeroLength,
+    SrtTranscriptType,
+    DuplicateGuid,
+    DuplicateEpisodeNumber,
+    DuplicateEnclosureUrl,
+    LiveItemEndBeforeStart,
+    LiveItemLiveBeforeStart,
+    LiveItemLiveAfterEnd,
+    LiveItemStalePending,
+    LiveItemEndedWithoutReplay,
+    OverlappingLiveItems,
+    ItunesImageFormat,
+    ItunesOwnerMissingEmail,
+    PodcastGuidHashingMistake,
+    PodcastGuidMismatch,
+    ValueFeesExceedPayment,
+    ValueFeesTakeAll,
+    ValueNoShares,
+    ValueSharesNotHundred,
+    ValueDuplicateRecipient,
+    UndeclaredNamespace,
+    IncorrectNamespaceUri,
+    UnknownTag,
+    PersonRoleNotInGroup,
+    UnknownLicense,
+    DeprecatedLicense,
+    HttpContentType,
+    HttpUncompressed,
+    HttpNoCacheValidators,
+    RedirectChain,
+    TemporaryRedirect,
+    NewFeedUrlMismatch,
+    SelfLinkMismatch,
+    MovedFeedUnlocked,
+    ByteOrderMark,
+    NonUtf8Encoding,
+    EncodingMismatch,
+    InvalidXmlCharacter,
+    UnescapedCharacter,
+    UnclosedTag,
+    HtmlOutsideCdata,
+    DoubleEscapedHtml,
+    UnbalancedHtml,
+    UnsupportedHtmlTag,
+    RelativeHtmlLink,
+    DescriptionTooLong,
+}
+
+impl Rule {
+    /// Identifier, default severity and short title.
+    fn info(&self) -> (&'static str, Severity, &'static str) {
+        match self {
+            Rule::ItemMissingEnclosure => (
+                "item-missing-enclosure",
+                Severity::Warning,
+                "Item without an enclosure",
+            ),
+            Rule::ItemMissingGuid => (
+                "item-missing-guid",
+                Severity::Warning,
+                "Item without a GUID",
+            ),
+            Rule::ItemMissingPubDate => (
+                "item-missing-pub-date",
+                Severity::Warning,
+                "Item without a publication date",
+            ),
+            Rule::FutureDate => ("future-date", Severity::Warning, "Date in the future"),
+            Rule::EnclosureZeroLength => (
+                "enclosure-zero-length",
+                Severity::Info,
+                "Enclosure length of zero",
+            ),
+            Rule::SrtTranscriptType => (
+                "srt-transcript-type",
+                Severity::Warning,
+                "Deprecated SRT transcript type",
+            ),
+            Rule::DuplicateGuid => ("duplicate-guid", Severity::Error, "GUID shared by items"),
+            Rule::DuplicateEpisodeNumber => (
+                "duplicate-episode-number",
+                Severity::Warning,
+                "Episode number shared by items",
+            ),
+            Rule::DuplicateEnclosureUrl => (
+                "duplicate-enclosure-url",
+                Severity::Warning,
+                "Enclosure URL shared by items",
+            ),
+            Rule::LiveItemEndBeforeStart => (
+                "live-item-end-before-start",
+                Severity::Error,
+                "Live item ending before it starts",
+            ),
+            Rule::LiveItemLiveBeforeStart => (
+                "live-item-live-before-start",
+                Severity::Warning,
+                "Live item live before its start",
+            ),
+            Rule::LiveItemLiveAfterEnd => (
+                "live-item-live-after-end",
+                Severity::Warning,
+                "Live item still live after its end",
+            ),
+            Rule::LiveItemStalePending => (
+                "live-item-stale-pending",
+                Severity::Warning,
+                "Live item pending long after its start",
+            ),
+            Rule::LiveItemEndedWithoutReplay => (
+                "live-item-ended-without-replay",
+                Severity::Warning,
+                "Ended live item without a replay",
+            ),
+            Rule::OverlappingLiveItems => (
+                "overlapping-live-items",
+                Severity::Warning,
+                "Overlapping live items",
+            ),
+            Rule::ItunesImageFormat => (
+                "itunes-image-format",
+                Severity::Warning,
+                "Artwork not in JPEG or PNG format",
+            ),
+            Rule::ItunesOwnerMissingEmail => (
+                "itunes-owner-missing-email",
+                Severity::Warning,
+                "Owner without an email",
+            ),
+            Rule::PodcastGuidHashingMistake => (
+                "podcast-guid-hashing-mistake",
+                Severity::Warning,
+                "Podcast GUID derived from an unnormalized URL",
+            ),
+            Rule::PodcastGuidMismatch => (
+                "podcast-guid-mismatch",
+                Severity::Info,
+                "Podcast GUID not matching the feed URL",
+            ),
+            Rule::ValueFeesExceedPayment => (
+                "value-fees-exceed-payment",
+                Severity::Error,
+                "Fees of more than 100%",
+            ),
+            Rule::ValueFeesTakeAll => (
+                "value-fees-take-all",
+                Severity::Warning,
+                "Fees taking the whole payment",
+            ),
+            Rule::ValueNoShares => (
+                "value-no-shares",
+                Severity::Error,
+                "No recipient to receive the payment",
+            ),
+            Rule::ValueSharesNotHundred => (
+                "value-shares-not-hundred",
+                Severity::Info,
+                "Splits not adding up to 100",
+            ),
+            Rule::ValueDuplicateRecipient => (
+                "value-duplicate-recipient",
+                Severity::Warning,
+                "Recipient listed more than once",
+            ),
+            Rule::UndeclaredNamespace => (
+                "undeclared-namespace",
+                Severity::Error,
+                "Namespace prefix without a declaration",
+            ),
+            Rule::IncorrectNamespaceUri => (
+                "incorrect-namespace-uri",
+                Severity::Error,
+                "Namespace declared with an incorrect URI",
+            ),
+            Rule::UnknownTag => (
+                "unknown-tag",
+                Severity::Warning,
+                "Element not defined in its namespace",
+            ),
+            Rule::PersonRoleNotInGroup => (
+                "person-role-not-in-group",
+                Severity::Warning,
+                "Person role outside its group",
+            ),
+            Rule::UnknownLicense => (
+                "unknown-license",
+                Severity::Info,
+                "License not on the SPDX list",
+            ),
+            Rule::DeprecatedLicense => (
+                "deprecated-license",
+                Severity::Warning,
+                "Deprecated SPDX license identifier",
+            ),
+            Rule::HttpContentType => (
+                "http-content-type",
+                Severity::Warning,
+                "Feed not served as XML",
+            ),
+            Rule::HttpUncompressed => (
+                "http-uncompressed",
+                Severity::Warning,
+                "Feed served without compression",
+            ),
+            Rule::HttpNoCacheValidators => (
+                "http-no-cache-validators",
+                Severity::Warning,
+                "Feed served without ETag or Last-Modified",
+            ),
+            Rule::RedirectChain => (
+                "redirect-chain",
+                Severity::Warning,
+                "Feed URL redirected more than once",
+            ),
+            Rule::TemporaryRedirect => (
+                "temporary-redirect",
+                Severity::Warning,
+                "Feed URL redirected temporarily",
+            ),
+            Rule::NewFeedUrlMismatch => (
+                "new-feed-url-mismatch",
+                Severity::Warning,
+                "New feed URL not matching where the feed is served",
+            ),
+            Rule::SelfLinkMismatch => (
+                "self-link-mismatch",
+                Severity::Info,
+                "Atom self link not matching where the feed is served",
+            ),
+            Rule::MovedFeedUnlocked => (
+                "moved-feed-unlocked",
+                Severity::Info,
+                "Moved feed not locked",
+            ),
+            Rule::ByteOrderMark => (
+                "byte-order-mark",
+                Severity::Warning,
+                "Byte order mark at the start of the feed",
+            ),
+            Rule::NonUtf8Encoding => (
+                "non-utf8-encoding",
+                Severity::Warning,
+                "Feed not encoded in UTF-8",
+            ),
+            Rule::EncodingMismatch => (
+                "encoding-mismatch",
+                Severity::Error,
+                "Feed encoded differently than declared",
+            ),
+            Rule::InvalidXmlCharacter => (
+                "invalid-xml-character",
+                Severity::Error,
+                "Character not allowed in XML",
+            ),
+            Rule::UnescapedCharacter => (
+                "unescaped-character",
+                Severity::Error,
+                "Unescaped ampersand or less-than sign",
+            ),
+            Rule::UnclosedTag => (
+                "unclosed-tag",
+                Severity::Error,
+                "Unclosed or mismatched tag",
+            ),
+            Rule::HtmlOutsideCdata => (
+                "html-outside-cdata",
+                Severity::Error,
+                "HTML neither escaped nor wrapped in CDATA",
+            ),
+            Rule::DoubleEscapedHtml => (
+                "double-escaped-html",
+                Severity::Warning,
+                "HTML escaped twice",
+            ),
+            Rule::UnbalancedHtml => ("unbalanced-html", Severity::Warning, "Unbalanced HTML tags"),
+            Rule::UnsupportedHtmlTag => (
+                "unsupported-html-tag",
+                Severity::Info,
+                "HTML tag not supported by podcast apps",
+            ),
+            Rule::RelativeHtmlLink => (
+                "relative-html-link",
+                Severity::Warning,
+                "Relative link in HTML",
+            ),
+            Rule::DescriptionTooLong => (
+                "description-too-long",
+                Severity::Warning,
+                "Description longer than Apple Podcasts shows",
+            ),
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.info().0
+    }
+
+    /// Severity of the findings unless the user downgrades them.
+    pub fn severity(&self) -> Severity {
+        self.info().1
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.info().2
+    }
+
+    pub fn from_id(id: &str) -> Option<Rule> {
+        Rule::ALL.into_iter().find(|rule| rule.id() == id)
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        Rule::from_id(&s).ok_or_else(|| serde::de::Error::custom(format!("unknown rule \"{s}\"")))
+    }
+}
+
+/// Identifier of the check that produced `error` in `node`.
+pub fn rule_id(node: &Node, error: &Error) -> String {
+    let attr = |attr: &str| {
+        if attr == NODE_VALUE {
+            "value".to_string()
+        } else {
+            attr.to_string()
+        }
+    };
+    match error {
+        Error::MissingAttribute(a) => format!("{}.missing-attribute.{}", node.name, attr(a)),
+        Error::InvalidAttribute(a, _) | Error::InvalidAttributeWithReason(a, _, _) => {
+            format!("{}.invalid-attribute.{}", node.name, attr(a))
+        }
+        Error::MissingChild(tag_name) => format!("{}.missing-child.{}", node.name, tag_name),
+        Error::MultipleChildren(tag_name) => {
+            format!("{}.multiple-children.{}", node.name, tag_name)
+        }
+        Error::AttributeExceedsMaxLength(a, _, _) => {
+            format!("{}.max-length.{}", node.name, attr(a))
+        }
+        Error::Custom(rule, _) | Error::CustomWithExtraInfo(rule, _, _) => rule.id().to_string(),
+        Error::Downgraded(_, error) => rule_id(node, error),
+    }
+}
+
+/// What to do with the findings of a rule.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Info,
+    Warning,
+}
+
+/// Rules that the user has disabled or downgraded, by rule ID.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleSettings(pub Vec<(String, RuleLevel)>);
+
+impl RuleSettings {
+    pub fn level(&self, rule_id: &str) -> Option<RuleLevel> {
+        self.0
+            .iter()
+            .find(|(id, _)| id == rule_id)
+            .map(|(_, level)| *level)
+    }
+
+    /// Overrides the rule, or restores its default if `level` is `None`.
+    pub fn set(&mut self, rule_id: &str, level: Option<RuleLevel>) {
+        self.0.retain(|(id, _)| id != rule_id);
+        if let Some(level) = level {
+            self.0.push((rule_id.to_string(), level));
+        }
+    }
+
+    /// Removes the findings of disabled rules and lowers the severity of downgraded ones.
+    ///
+    /// Returns the number of removed findings. Rules can only be downgraded, so a rule set to
+    /// [`RuleLevel::Warning`] leaves info findings as they are.
+    pub fn apply(&self, report: &mut Report) -> usize {
+        if self.0.is_empty() {
+            return 0;
+        }
+        self.apply_to_node(&mut report.root)
+    }
+
+    /// Applies the settings to a finding of `node`, returning `None` if its rule is disabled.
+    ///
+    /// Findings that do not belong to an element, such as those about the HTTP response or the
+    /// encoding, are identified with [`Node::default`].
+    pub fn apply_to_error(&self, node: &Node, error: Error) -> Option<Error> {
+        let severity = match self.level(&rule_id(node, &error)) {
+            None => return Some(error),
+            Some(RuleLevel::Off) => return None,
+            Some(RuleLevel::Info) => Severity::Info,
+            Some(RuleLevel::Warning) => Severity::Warning,
+        };
+        if severity < error.severity() {
+            Some(Error::Downgraded(severity, Box::new(error)))
+        } else {
+            Some(error)
+        }
+    }
+
+    fn apply_to_node(&self, node: &mut Node) -> usize {
+        let mut suppressed = 0;
+        let errors = std::mem::take(&mut node.errors);
+        for error in errors {
+            match self.apply_to_error(node, error) {
+                Some(error) => node.errors.push(error),
+                None => suppressed += 1,
+            }
+        }
+        for child in &mut node.children {
+            suppressed += self.apply_to_node(child);
+        }
+        suppressed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::TagName;
+
+    #[test]
+    fn test_apply() {
+        let mut report = Report {
+            root: Node {
+                name: TagName(None, "item".to_string()),
+                errors: vec![
+                    Error::MissingAttribute(NODE_VALUE.to_string()),
+                    Error::Custom(Rule::ItemMissingGuid, String::new()),
+                    Error::Custom(Rule::EnclosureZeroLength, String::new()),
+                ],
+                ..Default::default()
+            },
+        };
+        assert_eq!(
+            rule_id(&report.root, &report.root.errors[0]),
+            "item.missing-attribute.value"
+        );
+
+        let mut settings = RuleSettings::default();
+        settings.set("item.missing-attribute.value", Some(RuleLevel::Warning));
+        settings.set("item-missing-guid", Some(RuleLevel::Off));
+        settings.set("enclosure-zero-length", Some(RuleLevel::Warning));
+        assert_eq!(settings.apply(&mut report), 1);
+
+        let severities: Vec<_> = report.root.errors.iter().map(|e| e.severity()).collect();
+        assert_eq!(severities, vec![Severity::Warning, Severity::Info]);
+        assert_eq!(
+            rule_id(&report.root, &report.root.errors[0]),
+            "item.missing-attribute.value"
+        );
+
+        // Findings outside the elements, such as those about the HTTP response.
+        let mut settings = RuleSettings::default();
+        settings.set("http-content-type", Some(RuleLevel::Info));
+        settings.set("byte-order-mark", Some(RuleLevel::Off));
+        let error = settings
+            .apply_to_error(
+                &Node::default(),
+                Error::Custom(Rule::HttpContentType, String::new()),
+            )
+            .unwrap();
+        assert_eq!(error.severity(), Severity::Info);
+        assert_eq!(rule_id(&Node::default(), &error), "http-content-type");
+        assert_eq!(
+            settings.apply_to_error(
+                &Node::default(),
+                Error::Custom(Rule::ByteOrderMark, String::new())
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,458 @@
+//! Explanations of the rules for the rule catalog.
+
+use super::super::Severity;
+use super::Rule;
+
+const RSS_SPEC: &str = "https://www.rssboard.org/rss-specification";
+pub(crate) const PODCAST_SPEC: &str = "https://podcastindex.org/namespace/1.0";
+const APPLE_REQUIREMENTS: &str = "https://podcasters.apple.com/support/823-podcast-requirements";
+
+/// Explanation of a rule.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RuleDoc {
+    pub id: String,
+    pub title: String,
+    /// Severity of the findings unless the user downgrades them.
+    pub severity: Severity,
+    /// What is checked and why, in Markdown.
+    pub description: String,
+    pub spec_url: String,
+    /// Snippet of a feed that breaks the rule.
+    pub bad_example: String,
+    /// Snippet of a feed that follows the rule.
+    pub good_example: String,
+}
+
+/// Looks up the explanation of a rule by its ID.
+pub fn rule_doc(id: &str) -> Option<RuleDoc> {
+    match Rule::from_id(id) {
+        Some(rule) => Some(custom_rule_doc(rule)),
+        None => structural_rule_doc(id),
+    }
+}
+
+fn custom_rule_doc(rule: Rule) -> RuleDoc {
+    let (description, spec_url, bad_example, good_example): (&str, String, &str, &str) = match rule {
+        Rule::ItemMissingEnclosure => (
+            "Every episode needs an `<enclosure>` pointing to its media file. RSS allows items without one, but most podcast apps skip such items entirely.",
+            format!("{RSS_SPEC}#ltenclosuregtSubelementOfLtitemgt"),
+            "<item>\n  <title>Episode 1</title>\n</item>",
+            "<item>\n  <title>Episode 1</title>\n  <enclosure url=\"https://example.com/ep1.mp3\" length=\"24986239\" type=\"audio/mpeg\"/>\n</item>",
+        ),
+        Rule::ItemMissingGuid => (
+            "Apps use `<guid>` to tell episodes apart. Without it, they fall back to the title or the enclosure URL, so editing either can make an episode show up twice or lose its played state.",
+            format!("{RSS_SPEC}#ltguidgtSubelementOfLtitemgt"),
+            "<item>\n  <title>Episode 1</title>\n</item>",
+            "<item>\n  <title>Episode 1</title>\n  <guid isPermaLink=\"false\">3f2a9c1e-episode-1</guid>\n</item>",
+        ),
+        Rule::ItemMissingPubDate => (
+            "Apps sort and group episodes by `<pubDate>`. Items without it may be shown in an unexpected order or as new every time the feed is refreshed.",
+            format!("{RSS_SPEC}#ltpubdategtSubelementOfLtitemgt"),
+            "<item>\n  <title>Episode 1</title>\n</item>",
+            "<item>\n  <title>Episode 1</title>\n  <pubDate>Tue, 10 Jan 2023 08:00:00 GMT</pubDate>\n</item>",
+        ),
+        Rule::FutureDate => (
+            "The date is more than a day in the future. Some apps hide items until their publication date, while others show them right away, so scheduled episodes should only be added to the feed when they are published.",
+            format!("{RSS_SPEC}#ltpubdategtSubelementOfLtitemgt"),
+            "<pubDate>Fri, 01 Jan 2100 08:00:00 GMT</pubDate>",
+            "<pubDate>Tue, 10 Jan 2023 08:00:00 GMT</pubDate>",
+        ),
+        Rule::EnclosureZeroLength => (
+            "The `length` attribute of `<enclosure>` is the size of the file in bytes. Zero is commonly used when the size is unknown, which is allowed, but apps then cannot show the download size in advance.",
+            format!("{RSS_SPEC}#ltenclosuregtSubelementOfLtitemgt"),
+            "<enclosure url=\"https://example.com/ep1.mp3\" length=\"0\" type=\"audio/mpeg\"/>",
+            "<enclosure url=\"https://example.com/ep1.mp3\" length=\"24986239\" type=\"audio/mpeg\"/>",
+        ),
+        Rule::SrtTranscriptType => (
+            "`application/srt` is not a registered MIME type. The specification now uses `application/x-subrip` for SubRip transcripts, and apps may not recognize the old value.",
+            format!("{PODCAST_SPEC}#transcript"),
+            "<podcast:transcript url=\"https://example.com/ep1.srt\" type=\"application/srt\"/>",
+            "<podcast:transcript url=\"https://example.com/ep1.srt\" type=\"application/x-subrip\"/>",
+        ),
+        Rule::DuplicateGuid => (
+            "A `<guid>` must identify a single item. When items share one, apps treat them as the same episode and usually show only one of them.",
+            format!("{RSS_SPEC}#ltguidgtSubelementOfLtitemgt"),
+            "<item><guid isPermaLink=\"false\">episode</guid></item>\n<item><guid isPermaLink=\"false\">episode</guid></item>",
+            "<item><guid isPermaLink=\"false\">episode-1</guid></item>\n<item><guid isPermaLink=\"false\">episode-2</guid></item>",
+        ),
+        Rule::DuplicateEpisodeNumber => (
+            "Two items claim the same `<podcast:episode>` number within the same `<podcast:season>`. This is usually a copy-paste mistake, and apps that sort by episode number will order the items arbitrarily.",
+            format!("{PODCAST_SPEC}#episode"),
+            "<item><podcast:season>1</podcast:season><podcast:episode>3</podcast:episode></item>\n<item><podcast:season>1</podcast:season><podcast:episode>3</podcast:episode></item>",
+            "<item><podcast:season>1</podcast:season><podcast:episode>3</podcast:episode></item>\n<item><podcast:season>1</podcast:season><podcast:episode>4</podcast:episode></item>",
+        ),
+        Rule::DuplicateEnclosureUrl => (
+            "Two items point to the same media file. Some apps use the enclosure URL to identify episodes, so they may merge the items or download the file only once.",
+            format!("{RSS_SPEC}#ltenclosuregtSubelementOfLtitemgt"),
+            "<item><enclosure url=\"https://example.com/ep1.mp3\" length=\"1\" type=\"audio/mpeg\"/></item>\n<item><enclosure url=\"https://example.com/ep1.mp3\" length=\"1\" type=\"audio/mpeg\"/></item>",
+            "<item><enclosure url=\"https://example.com/ep1.mp3\" length=\"1\" type=\"audio/mpeg\"/></item>\n<item><enclosure url=\"https://example.com/ep2.mp3\" length=\"1\" type=\"audio/mpeg\"/></item>",
+        ),
+        Rule::LiveItemEndBeforeStart => (
+            "The `end` of a `<podcast:liveItem>` has to be after its `start`. Apps use both to show when the stream is available.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"pending\" start=\"2023-01-10T20:00:00Z\" end=\"2023-01-10T19:00:00Z\">",
+            "<podcast:liveItem status=\"pending\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">",
+        ),
+        Rule::LiveItemLiveBeforeStart => (
+            "The status is `live` more than an hour before the scheduled `start`. Listeners will be told the show is on air while the stream has not started; use `pending` until it does.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"live\" start=\"2100-01-10T19:00:00Z\" end=\"2100-01-10T20:00:00Z\">",
+            "<podcast:liveItem status=\"pending\" start=\"2100-01-10T19:00:00Z\" end=\"2100-01-10T20:00:00Z\">",
+        ),
+        Rule::LiveItemLiveAfterEnd => (
+            "The status is still `live` more than an hour after the scheduled `end`. Switch it to `ended` once the show is over, or move the `end` if the show runs late.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"live\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">",
+            "<podcast:liveItem status=\"ended\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">",
+        ),
+        Rule::LiveItemStalePending => (
+            "The status is `pending` although the show was scheduled to start more than a day ago. Update the status, reschedule the show, or remove the item if the show was cancelled.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"pending\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">",
+            "<podcast:liveItem status=\"ended\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">",
+        ),
+        Rule::LiveItemEndedWithoutReplay => (
+            "Once a live show has `ended`, the item can offer a recording of it. Without an `<enclosure>` or `<podcast:alternateEnclosure>`, listeners who missed the show have nothing to play.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"ended\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">\n  <title>Live show</title>\n</podcast:liveItem>",
+            "<podcast:liveItem status=\"ended\" start=\"2023-01-10T19:00:00Z\" end=\"2023-01-10T20:00:00Z\">\n  <title>Live show</title>\n  <enclosure url=\"https://example.com/replay.mp3\" length=\"24986239\" type=\"audio/mpeg\"/>\n</podcast:liveItem>",
+        ),
+        Rule::OverlappingLiveItems => (
+            "The scheduled times of two `<podcast:liveItem>`s overlap. A show usually has a single stream, so this tends to be a scheduling mistake.",
+            format!("{PODCAST_SPEC}#live-item"),
+            "<podcast:liveItem status=\"pending\" start=\"2100-01-10T19:00:00Z\" end=\"2100-01-10T21:00:00Z\">\n<podcast:liveItem status=\"pending\" start=\"2100-01-10T20:00:00Z\" end=\"2100-01-10T22:00:00Z\">",
+            "<podcast:liveItem status=\"pending\" start=\"2100-01-10T19:00:00Z\" end=\"2100-01-10T20:00:00Z\">\n<podcast:liveItem status=\"pending\" start=\"2100-01-17T19:00:00Z\" end=\"2100-01-17T20:00:00Z\">",
+        ),
+        Rule::ItunesImageFormat => (
+            "Apple Podcasts only accepts artwork in JPEG or PNG format. The URL of `<itunes:image>` does not end with a matching extension, which often means the image is in another format.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<itunes:image href=\"https://example.com/cover.webp\"/>",
+            "<itunes:image href=\"https://example.com/cover.jpg\"/>",
+        ),
+        Rule::ItunesOwnerMissingEmail => (
+            "Apple Podcasts and other directories send a verification code to the email in `<itunes:owner>` when the show is submitted or moved.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<itunes:owner>\n  <itunes:name>Jane Doe</itunes:name>\n</itunes:owner>",
+            "<itunes:owner>\n  <itunes:name>Jane Doe</itunes:name>\n  <itunes:email>jane@example.com</itunes:email>\n</itunes:owner>",
+        ),
+        Rule::PodcastGuidHashingMistake => (
+            "The `<podcast:guid>` is a UUIDv5 of the feed URL with the protocol scheme and trailing slashes removed. The declared GUID matches a variant of the URL in which they were kept, so it was generated incorrectly. Since the GUID must never change, only fix this if the feed has not been published with it yet.",
+            format!("{PODCAST_SPEC}#guid"),
+            "<!-- uuidv5(\"https://example.com/feed/\") -->\n<podcast:guid>…</podcast:guid>",
+            "<!-- uuidv5(\"example.com/feed\") -->\n<podcast:guid>…</podcast:guid>",
+        ),
+        Rule::PodcastGuidMismatch => (
+            "The `<podcast:guid>` does not match any URL known for the feed. This is expected when the feed has moved since the GUID was generated, because the GUID must stay the same when it does.",
+            format!("{PODCAST_SPEC}#guid"),
+            "<podcast:guid>00000000-0000-0000-0000-000000000000</podcast:guid>",
+            "<podcast:guid>917393e3-1b1e-5cef-ace4-edaa54e1f810</podcast:guid>",
+        ),
+        Rule::ValueFeesExceedPayment => (
+            "Recipients with `fee=\"true\"` receive their `split` as a percentage of every payment before the rest is shared. Together they cannot take more than 100%.",
+            format!("{PODCAST_SPEC}#value-recipient"),
+            "<podcast:valueRecipient name=\"Host\" split=\"60\" fee=\"true\" …/>\n<podcast:valueRecipient name=\"App\" split=\"60\" fee=\"true\" …/>",
+            "<podcast:valueRecipient name=\"Host\" split=\"95\" …/>\n<podcast:valueRecipient name=\"App\" split=\"5\" fee=\"true\" …/>",
+        ),
+        Rule::ValueFeesTakeAll => (
+            "Fee recipients take 100% of every payment, so the other recipients never receive anything.",
+            format!("{PODCAST_SPEC}#value-recipient"),
+            "<podcast:valueRecipient name=\"Host\" split=\"90\" …/>\n<podcast:valueRecipient name=\"App\" split=\"100\" fee=\"true\" …/>",
+            "<podcast:valueRecipient name=\"Host\" split=\"90\" …/>\n<podcast:valueRecipient name=\"App\" split=\"10\" fee=\"true\" …/>",
+        ),
+        Rule::ValueNoShares => (
+            "After fees, the payment is divided between the other recipients in proportion to their `split`. If none of them has a positive split, there is nobody to pay.",
+            format!("{PODCAST_SPEC}#value-recipient"),
+            "<podcast:valueRecipient name=\"Host\" split=\"0\" …/>",
+            "<podcast:valueRecipient name=\"Host\" split=\"100\" …/>",
+        ),
+        Rule::ValueSharesNotHundred => (
+            "Splits are shares rather than percentages, so they do not have to add up to 100. Many publishers expect them to, though, so the effective percentage of each recipient is shown next to it in the report.",
+            format!("{PODCAST_SPEC}#value-recipient"),
+            "<podcast:valueRecipient name=\"Host\" split=\"50\" …/>\n<podcast:valueRecipient name=\"Guest\" split=\"25\" …/>",
+            "<podcast:valueRecipient name=\"Host\" split=\"75\" …/>\n<podcast:valueRecipient name=\"Guest\" split=\"25\" …/>",
+        ),
+        Rule::ValueDuplicateRecipient => (
+            "The same recipient, with the same address and custom record, is listed more than once in a `<podcast:value>` block. Some apps send a separate payment for each entry, which costs extra fees; combine their splits instead.",
+            format!("{PODCAST_SPEC}#value-recipient"),
+            "<podcast:valueRecipient name=\"Host\" type=\"node\" address=\"02d5…\" split=\"50\"/>\n<podcast:valueRecipient name=\"Host\" type=\"node\" address=\"02d5…\" split=\"50\"/>",
+            "<podcast:valueRecipient name=\"Host\" type=\"node\" address=\"02d5…\" split=\"100\"/>",
+        ),
+        Rule::UndeclaredNamespace => (
+            "Elements such as `<podcast:transcript>` only belong to a namespace if their prefix is declared with an `xmlns` attribute, usually on `<rss>`. Apps that parse namespaces properly ignore elements whose prefix is not declared, and some refuse the whole feed.",
+            PODCAST_SPEC.to_string(),
+            "<rss version=\"2.0\">\n  <channel>\n    <podcast:locked>yes</podcast:locked>",
+            "<rss version=\"2.0\" xmlns:podcast=\"https://podcastindex.org/namespace/1.0\">\n  <channel>\n    <podcast:locked>yes</podcast:locked>",
+        ),
+        Rule::IncorrectNamespaceUri => (
+            "Apps recognize a namespace by its URI, not by its prefix. If the URI differs from the one in the specification, even by a trailing slash, the elements using the prefix are not recognized.",
+            PODCAST_SPEC.to_string(),
+            "<rss version=\"2.0\" xmlns:podcast=\"https://github.com/Podcastindex-org/podcast-namespace/blob/main/docs/1.0.md\">",
+            "<rss version=\"2.0\" xmlns:podcast=\"https://podcastindex.org/namespace/1.0\">",
+        ),
+        Rule::UnknownTag => (
+            "The element is in the podcast or iTunes namespace, but the namespace does not define it. This is usually a typo, so apps ignore the element.",
+            PODCAST_SPEC.to_string(),
+            "<podcast:transcripts url=\"https://example.com/ep1.vtt\" type=\"text/vtt\"/>",
+            "<podcast:transcript url=\"https://example.com/ep1.vtt\" type=\"text/vtt\"/>",
+        ),
+        Rule::PersonRoleNotInGroup => (
+            "The podcast taxonomy assigns every `role` of `<podcast:person>` to a `group`. If the group is omitted, it is assumed to be \"cast\", so roles from other groups need an explicit `group` attribute.",
+            format!("{PODCAST_SPEC}#person"),
+            "<podcast:person role=\"producer\">Jane Doe</podcast:person>",
+            "<podcast:person group=\"creative direction\" role=\"producer\">Jane Doe</podcast:person>",
+        ),
+        Rule::UnknownLicense => (
+            "The value of `<podcast:license>` should be an identifier from the [SPDX license list](https://spdx.org/licenses/), so that apps know what the license allows. Other licenses are allowed, but then the `url` attribute has to point to the license text.",
+            format!("{PODCAST_SPEC}#license"),
+            "<podcast:license>Creative Commons Attribution</podcast:license>",
+            "<podcast:license>CC-BY-4.0</podcast:license>",
+        ),
+        Rule::DeprecatedLicense => (
+            "SPDX has replaced some identifiers with more precise ones. For example, `GPL-2.0` became `GPL-2.0-only` and `GPL-2.0-or-later`, depending on whether later versions of the license may be used.",
+            format!("{PODCAST_SPEC}#license"),
+            "<podcast:license>GPL-2.0</podcast:license>",
+            "<podcast:license>GPL-2.0-only</podcast:license>",
+        ),
+        Rule::HttpContentType => (
+            "The `Content-Type` header tells apps what the response contains. Feeds should be served as `application/rss+xml`, `application/xml` or `text/xml`; a feed served as `text/html` is often an error page or gets rejected by directories.",
+            "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Type".to_string(),
+            "Content-Type: text/html",
+            "Content-Type: application/rss+xml; charset=utf-8",
+        ),
+        Rule::HttpUncompressed => (
+            "Apps and directories fetch feeds many times a day. XML compresses very well, so serving the feed with gzip or Brotli compression saves a lot of bandwidth for both the host and the listeners.",
+            "https://developer.mozilla.org/en-US/docs/Web/HTTP/Compression".to_string(),
+            "Content-Type: application/rss+xml\nContent-Length: 2400000",
+            "Content-Type: application/rss+xml\nContent-Encoding: gzip\nContent-Length: 310000",
+        ),
+        Rule::HttpNoCacheValidators => (
+            "With an `ETag` or `Last-Modified` header, apps can send a conditional request and get an empty `304 Not Modified` response while the feed is unchanged. Without them, the whole feed is downloaded on every refresh.",
+            "https://developer.mozilla.org/en-US/docs/Web/HTTP/Conditional_requests".to_string(),
+            "HTTP/1.1 200 OK\nContent-Type: application/rss+xml",
+            "HTTP/1.1 200 OK\nContent-Type: application/rss+xml\nETag: \"33a64df5\"\nLast-Modified: Tue, 10 Jan 2023 08:00:00 GMT",
+        ),
+        Rule::RedirectChain => (
+            "Every redirect is an extra request on every refresh of every app. When a feed moves more than once, redirect each old URL straight to the current one instead of chaining the redirects.",
+            "https://developer.mozilla.org/en-US/docs/Web/HTTP/Redirections".to_string(),
+            "https://old.example.com/feed → 301 → https://example.com/feed → 301 → https://new.example.com/feed",
+            "https://old.example.com/feed → 301 → https://new.example.com/feed\nhttps://example.com/feed → 301 → https://new.example.com/feed",
+        ),
+        Rule::TemporaryRedirect => (
+            "A `302`, `303` or `307` redirect tells apps that the feed will be back at the old URL, so they never update their subscriptions. A feed that has moved should be redirected with `301` or `308`.",
+            "https://developer.mozilla.org/en-US/docs/Web/HTTP/Redirections".to_string(),
+            "HTTP/1.1 302 Found\nLocation: https://new.example.com/feed",
+            "HTTP/1.1 301 Moved Permanently\nLocation: https://new.example.com/feed",
+        ),
+        Rule::NewFeedUrlMismatch => (
+            "When a feed moves, Apple Podcasts expects both a redirect from the old URL and `<itunes:new-feed-url>` with the new URL in the feed. The tag has to point to the URL that actually serves the feed.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<!-- served from https://new.example.com/feed -->\n<itunes:new-feed-url>https://old.example.com/feed</itunes:new-feed-url>",
+            "<!-- served from https://new.example.com/feed -->\n<itunes:new-feed-url>https://new.example.com/feed</itunes:new-feed-url>",
+        ),
+        Rule::SelfLinkMismatch => (
+            "`<atom:link rel=\"self\">` is where the feed says it can be found. Some apps and aggregators subscribe to that URL, so it should be the one the feed is served from.",
+            "https://validator.w3.org/feed/docs/warning/MissingAtomSelfLink.html".to_string(),
+            "<!-- served from https://new.example.com/feed -->\n<atom:link href=\"https://old.example.com/feed\" rel=\"self\" type=\"application/rss+xml\"/>",
+            "<!-- served from https://new.example.com/feed -->\n<atom:link href=\"https://new.example.com/feed\" rel=\"self\" type=\"application/rss+xml\"/>",
+        ),
+        Rule::MovedFeedUnlocked => (
+            "`<podcast:locked>` tells platforms whether they may import the feed. Once a feed has moved to its new host, locking it prevents anyone else from importing it.",
+            format!("{PODCAST_SPEC}#locked"),
+            "<podcast:locked>no</podcast:locked>",
+            "<podcast:locked owner=\"jane@example.com\">yes</podcast:locked>",
+        ),
+        Rule::ByteOrderMark => (
+            "A byte order mark is an invisible character that some editors put at the start of UTF-8 files. XML allows it, but some apps expect the feed to start with `<?xml` and fail to read it. Save the feed as UTF-8 without a byte order mark.",
+            "https://www.w3.org/TR/xml/#charencoding".to_string(),
+            "\u{FEFF}<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+        ),
+        Rule::NonUtf8Encoding => (
+            "XML can be encoded in many ways, but UTF-8 is the only encoding that every app reads. Feeds in other encodings often end up with garbled characters in titles and descriptions.",
+            "https://www.w3.org/TR/xml/#charencoding".to_string(),
+            "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+        ),
+        Rule::EncodingMismatch => (
+            "The encoding in the XML declaration tells apps how to read the bytes of the feed, and it is UTF-8 if omitted. When the feed is actually encoded differently, characters outside ASCII are garbled or the feed cannot be read at all.",
+            "https://www.w3.org/TR/xml/#charencoding".to_string(),
+            "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<!-- saved as UTF-8 -->\n<title>Café</title>",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- saved as UTF-8 -->\n<title>Café</title>",
+        ),
+        Rule::InvalidXmlCharacter => (
+            "XML does not allow most control characters, such as the ones that word processors leave behind when text is pasted. They cannot be escaped either, so they have to be removed.",
+            "https://www.w3.org/TR/xml/#charsets".to_string(),
+            "<title>Episode 1&#8;</title>",
+            "<title>Episode 1</title>",
+        ),
+        Rule::UnescapedCharacter => (
+            "In XML, `&` starts a reference and `<` starts a tag, so they have to be written as `&amp;` and `&lt;` in text. XML only defines `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`, so HTML entities like `&nbsp;` have to be written as character references, or the text wrapped in CDATA.",
+            "https://www.w3.org/TR/xml/#syntax".to_string(),
+            "<title>Q&A &nbsp;</title>",
+            "<title>Q&amp;A &#160;</title>",
+        ),
+        Rule::UnclosedTag => (
+            "Every tag has to be closed, and in the reverse order of opening. A single unclosed tag makes the whole feed unreadable to XML parsers.",
+            "https://www.w3.org/TR/xml/#sec-starttags".to_string(),
+            "<item>\n  <title>Episode 1\n</item>",
+            "<item>\n  <title>Episode 1</title>\n</item>",
+        ),
+        Rule::HtmlOutsideCdata => (
+            "HTML in `<description>`, `<content:encoded>` and `<itunes:summary>` has to be either escaped or wrapped in CDATA. Otherwise XML parsers read the HTML tags as elements of the feed, and apps show the text without them, or not at all.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<description><p>Notes</p></description>",
+            "<description><![CDATA[<p>Notes</p>]]></description>",
+        ),
+        Rule::DoubleEscapedHtml => (
+            "HTML that is escaped and then also escaped again or wrapped in CDATA reaches apps as escaped text, so listeners see the tags instead of the formatting.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<description><![CDATA[&lt;p&gt;Notes&lt;/p&gt;]]></description>",
+            "<description><![CDATA[<p>Notes</p>]]></description>",
+        ),
+        Rule::UnbalancedHtml => (
+            "Apps render descriptions as HTML, so a tag that is never closed formats the rest of the text, and closing tags without opening ones may be shown as text.",
+            "https://html.spec.whatwg.org/multipage/syntax.html#elements-2".to_string(),
+            "<![CDATA[<b>New episode<p>With a guest]]>",
+            "<![CDATA[<b>New episode</b><p>With a guest</p>]]>",
+        ),
+        Rule::UnsupportedHtmlTag => (
+            "Apple Podcasts renders only `<p>`, `<ol>`, `<ul>`, `<li>` and `<a>`, and other major apps add little more than `<br>`, `<b>`, `<strong>`, `<i>` and `<em>`. Other tags are stripped, or their contents shown as text.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<![CDATA[<div><img src=\"https://example.com/ad.png\"><span>Notes</span></div>]]>",
+            "<![CDATA[<p>Notes</p>]]>",
+        ),
+        Rule::RelativeHtmlLink => (
+            "Apps show descriptions outside of the website, so links relative to it lead nowhere. Links in descriptions have to be absolute URLs.",
+            "https://developer.mozilla.org/en-US/docs/Learn/Common_questions/Web_mechanics/What_is_a_URL#absolute_urls_vs._relative_urls".to_string(),
+            "<![CDATA[<a href=\"/episodes/1\">Transcript</a>]]>",
+            "<![CDATA[<a href=\"https://example.com/episodes/1\">Transcript</a>]]>",
+        ),
+        Rule::DescriptionTooLong => (
+            "Apple Podcasts shows at most 4000 bytes of a description, including its HTML, and cuts off the rest. Put the most important information first, and link to full show notes instead.",
+            APPLE_REQUIREMENTS.to_string(),
+            "<description><!-- 6000 bytes of show notes --></description>",
+            "<description><![CDATA[<p>Summary</p><p><a href=\"https://example.com/episodes/1\">Full show notes</a></p>]]></description>",
+        ),
+    };
+
+    RuleDoc {
+        id: rule.id().to_string(),
+        title: rule.title().to_string(),
+        severity: rule.severity(),
+        description: description.to_string(),
+        spec_url,
+        bad_example: bad_example.to_string(),
+        good_example: good_example.to_string(),
+    }
+}
+
+/// Explains IDs such as `podcast:transcript.missing-attribute.url`.
+fn structural_rule_doc(id: &str) -> Option<RuleDoc> {
+    let (tag, rest) = id.split_once('.')?;
+    let (kind, target) = rest.split_once('.')?;
+    if tag.is_empty() || target.is_empty() {
+        return None;
+    }
+
+    let (what, subject) = if target == "value" {
+        ("the value of".to_string(), format!("value of <{tag}>"))
+    } else {
+        (
+            format!("attribute `{target}` of"),
+            format!("attribute {target} of <{tag}>"),
+        )
+    };
+    let attr_example = |value: &str| {
+        if target == "value" {
+            format!("<{tag}>{value}</{tag}>")
+        } else {
+            format!("<{tag} {target}=\"{value}\" …/>")
+        }
+    };
+
+    let (title, description, bad_example, good_example) = match kind {
+        "missing-attribute" => (
+            format!("Missing {subject}"),
+            format!("The specification requires {what} `<{tag}>`, and apps may ignore the element without it."),
+            if target == "value" {
+                format!("<{tag}></{tag}>")
+            } else {
+                format!("<{tag} …/>")
+            },
+            attr_example("…"),
+        ),
+        "invalid-attribute" => (
+            format!("Invalid {subject}"),
+            format!("The specification restricts {what} `<{tag}>` to a particular format, such as a URL, a number, a date or one of a few allowed values. The finding explains what is wrong with the value."),
+            attr_example("not valid"),
+            attr_example("…"),
+        ),
+        "max-length" => (
+            format!("Too long {subject}"),
+            format!("The specification limits the length of {what} `<{tag}>`. Apps may truncate longer values or reject the element."),
+            attr_example("a very long value …"),
+            attr_example("a short value"),
+        ),
+        "missing-child" => (
+            format!("<{tag}> without <{target}>"),
+            format!("`<{tag}>` must contain `<{target}>`."),
+            format!("<{tag}>\n  …\n</{tag}>"),
+            format!("<{tag}>\n  <{target}>…</{target}>\n</{tag}>"),
+        ),
+        "multiple-children" => (
+            format!("<{tag}> with multiple <{target}>"),
+            format!("`<{tag}>` may contain at most one `<{target}>`. When there are more, apps pick one of them arbitrarily."),
+            format!("<{tag}>\n  <{target}>…</{target}>\n  <{target}>…</{target}>\n</{tag}>"),
+            format!("<{tag}>\n  <{target}>…</{target}>\n</{tag}>"),
+        ),
+        _ => return None,
+    };
+
+    Some(RuleDoc {
+        id: id.to_string(),
+        title,
+        severity: Severity::Error,
+        description,
+        spec_url: spec_url(tag),
+        bad_example,
+        good_example,
+    })
+}
+
+/// Section of the specification that defines the tag.
+fn spec_url(tag: &str) -> String {
+    match tag.split_once(':') {
+        Some(("podcast", name)) => {
+            let mut anchor = String::new();
+            for c in name.chars() {
+                if c.is_ascii_uppercase() {
+                    anchor.push('-');
+                }
+                anchor.push(c.to_ascii_lowercase());
+            }
+            format!("{PODCAST_SPEC}#{anchor}")
+        }
+        Some(("itunes", _)) => APPLE_REQUIREMENTS.to_string(),
+        _ => RSS_SPEC.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_doc() {
+        let doc = rule_doc("podcast:liveItem.missing-attribute.start").unwrap();
+        assert_eq!(doc.title, "Missing attribute start of <podcast:liveItem>");
+        assert_eq!(
+            doc.spec_url,
+            "https://podcastindex.org/namespace/1.0#live-item"
+        );
+
+        assert!(Rule::ALL
+            .into_iter()
+            .all(|rule| rule_doc(rule.id()).is_some()));
+        assert_eq!(rule_doc("podcast:guid"), None);
+    }
+}
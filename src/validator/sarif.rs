@@ -0,0 +1,137 @@
+//! Export of validation reports in the [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format understood by code-scanning dashboards.
+
+use super::rules::rule_id;
+use super::{Error, Node, Report, Severity};
+use serde_json::json;
+use std::collections::HashMap;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Converts the report to a SARIF log with a single run.
+///
+/// Findings are located by their path in the feed, e.g. `rss/channel[1]/item[3]/podcast:transcript[1]`.
+/// If `feed_uri` is given, it is used as the artifact location of every finding, and the findings
+/// are also located in the source. SARIF does not allow a region without an artifact.
+pub fn to_sarif(report: &Report, feed_uri: Option<&str>) -> serde_json::Value {
+    let mut findings = Vec::new();
+    collect_findings(&report.root, report.root.name.to_string(), &mut findings);
+
+    let mut rule_ids: Vec<String> = findings
+        .iter()
+        .map(|(_, node, error)| rule_id(node, error))
+        .collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+    let rules: Vec<_> = rule_ids.iter().map(|id| json!({ "id": id })).collect();
+
+    let results: Vec<_> = findings
+        .iter()
+        .map(|(path, node, error)| {
+            let mut location = json!({
+                "logicalLocations": [{ "fullyQualifiedName": path, "kind": "element" }],
+            });
+            if let Some(uri) = feed_uri {
+                location["physicalLocation"] = json!({ "artifactLocation": { "uri": uri } });
+                if let Some(span) = node.error_span(error) {
+                    location["physicalLocation"]["region"] = json!({
+                        "startLine": span.line,
+                        "startColumn": span.column,
+                        "byteOffset": span.start,
+                        "byteLength": span.end - span.start,
+                    });
+                }
+            }
+
+            json!({
+                "ruleId": rule_id(node, error),
+                "level": level(error.severity()),
+                "message": { "text": error.to_string() },
+                "locations": [location],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "RSS Blue Podcast Validator",
+                    "informationUri": "https://tools.rssblue.com/validator",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+            // Columns count characters rather than UTF-16 code units.
+            "columnKind": "unicodeCodePoints",
+        }],
+    })
+}
+
+fn collect_findings<'a>(
+    node: &'a Node,
+    path: String,
+    findings: &mut Vec<(String, &'a Node, &'a Error)>,
+) {
+    for error in &node.errors {
+        findings.push((path.clone(), node, error));
+    }
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for child in &node.children {
+        let name = child.name.to_string();
+        let count = counts.entry(name.clone()).or_insert(0);
+        *count += 1;
+        collect_findings(child, format!("{path}/{name}[{count}]"), findings);
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::validate_str;
+    use super::*;
+
+    #[test]
+    fn test_to_sarif() {
+        let before_item = "<rss version=\"2.0\"><channel><title>Café</title>";
+        let item = "<item><title>1</title><guid>a</guid></item>";
+        let report = validate_str(&format!("{before_item}{item}</channel></rss>")).unwrap();
+
+        let sarif = to_sarif(&report, Some("https://example.com/feed.xml"));
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let missing_enclosure = results
+            .iter()
+            .find(|r| r["ruleId"] == "item-missing-enclosure")
+            .unwrap();
+        assert_eq!(missing_enclosure["level"], "warning");
+        let location = &missing_enclosure["locations"][0];
+        assert_eq!(
+            location["logicalLocations"][0]["fullyQualifiedName"],
+            "rss/channel[1]/item[1]"
+        );
+        // “é” takes two bytes, but the column counts it once.
+        assert_eq!(
+            location["physicalLocation"]["region"],
+            json!({
+                "startLine": 1,
+                "startColumn": before_item.chars().count() + 1,
+                "byteOffset": before_item.len(),
+                "byteLength": item.len(),
+            })
+        );
+
+        let sarif = to_sarif(&report, None);
+        let location = &sarif["runs"][0]["results"][0]["locations"][0];
+        assert!(location.get("physicalLocation").is_none());
+    }
+}
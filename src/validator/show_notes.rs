@@ -0,0 +1,383 @@
+//! HTML in descriptions and show notes.
+//!
+//! badpod only keeps the text of these elements, so they are checked in the source, where it is
+//! still visible whether the HTML was escaped or wrapped in CDATA.
+
+use super::source::Element;
+use super::{decode_entities, escape_html, Error, Node, Rule};
+use url::Url;
+
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+
+/// Tags that the major podcast apps render. Apple Podcasts supports only `<p>`, `<ol>`, `<ul>`,
+/// `<li>` and `<a>`.
+const SUPPORTED_TAGS: [&str; 10] = ["a", "b", "br", "em", "i", "li", "ol", "p", "strong", "ul"];
+
+/// Elements that have no content and no closing tag.
+const VOID_TAGS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose closing tag HTML allows to omit.
+const OPTIONAL_END_TAGS: [&str; 10] = [
+    "dd", "dt", "li", "option", "p", "tbody", "td", "th", "thead", "tr",
+];
+
+/// Characters after which Apple Podcasts cuts descriptions off.
+const MAX_LENGTH: usize = 4000;
+
+/// Checks the descriptions of the channel and of its items, reporting the findings on the channel
+/// and item nodes.
+pub(super) fn analyze(root: &mut Node, element: &Element, source: &str) {
+    let channel_node = root
+        .children
+        .iter_mut()
+        .find(|n| n.name.to_string() == "channel");
+    let channel_element = element.children.iter().find(|e| e.name == "channel");
+    let (channel_node, channel_element) = match (channel_node, channel_element) {
+        (Some(node), Some(element)) => (node, element),
+        _ => return,
+    };
+
+    analyze_fields(channel_node, channel_element, source);
+    for name in ["item", "podcast:liveItem"] {
+        let nodes = channel_node
+            .children
+            .iter_mut()
+            .filter(|n| n.name.to_string() == name);
+        let elements = channel_element.children.iter().filter(|e| e.name == name);
+        for (node, element) in nodes.zip(elements) {
+            analyze_fields(node, element, source);
+        }
+    }
+}
+
+fn analyze_fields(node: &mut Node, element: &Element, source: &str) {
+    for child in &element.children {
+        let name = match (child.name.as_str(), child.namespace.as_deref()) {
+            ("description", None) => "description",
+            ("itunes:summary", _) => "itunes:summary",
+            (name, Some(CONTENT_NAMESPACE)) if name.ends_with(":encoded") => "content:encoded",
+            _ => continue,
+        };
+        let content = match child.content {
+            Some(content) => &source[content.start..content.end],
+            None => continue,
+        };
+        node.errors
+            .extend(analyze_html(name, content, !child.children.is_empty()));
+    }
+}
+
+/// Checks the raw content of an element, `has_elements` telling whether the XML parser sees
+/// elements in it.
+fn analyze_html(name: &str, raw: &str, has_elements: bool) -> Vec<Error> {
+    let mut errors = Vec::new();
+    let field = format!("<code>&lt;{name}&gt;</code>");
+    let html = text(raw);
+
+    if has_elements {
+        errors.push(Error::Custom(
+            Rule::HtmlOutsideCdata,
+            format!("{field} contains HTML that is neither escaped nor wrapped in CDATA, so XML parsers read its tags as elements and apps lose them."),
+        ));
+    }
+    let is_double_escaped = html
+        .match_indices("&lt;")
+        .any(|(i, _)| html[i + 4..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/'));
+    if is_double_escaped {
+        errors.push(Error::Custom(
+            Rule::DoubleEscapedHtml,
+            format!("{field} contains tags that are escaped twice, such as <code>&amp;lt;p&amp;gt;</code>, so apps show them as text. Escape the HTML once, or wrap it in CDATA without escaping it."),
+        ));
+    }
+
+    let tags = tags(&html);
+
+    let mut problems = Vec::new();
+    let mut stack: Vec<&str> = Vec::new();
+    for tag in &tags {
+        if VOID_TAGS.contains(&tag.name.as_str()) {
+            continue;
+        }
+        if !tag.is_closing {
+            if !tag.is_self_closing {
+                stack.push(&tag.name);
+            }
+            continue;
+        }
+        match stack.iter().rposition(|open| *open == tag.name) {
+            Some(pos) => {
+                for open in stack.drain(pos..).skip(1) {
+                    if !OPTIONAL_END_TAGS.contains(&open) {
+                        problems.push(format!(
+                            "<code>&lt;{}&gt;</code> is not closed before <code>&lt;/{}&gt;</code>",
+                            escape_html(open),
+                            escape_html(&tag.name)
+                        ));
+                    }
+                }
+            }
+            None => problems.push(format!(
+                "<code>&lt;/{}&gt;</code> has no opening tag",
+                escape_html(&tag.name)
+            )),
+        }
+    }
+    for open in stack {
+        if !OPTIONAL_END_TAGS.contains(&open) {
+            problems.push(format!(
+                "<code>&lt;{}&gt;</code> is never closed",
+                escape_html(open)
+            ));
+        }
+    }
+    if !problems.is_empty() {
+        errors.push(Error::Custom(
+            Rule::UnbalancedHtml,
+            format!(
+                "{field} has unbalanced HTML: {}. Apps may format the rest of the text wrongly.",
+                problems.join(", ")
+            ),
+        ));
+    }
+
+    let mut unsupported: Vec<&str> = Vec::new();
+    for tag in &tags {
+        if !SUPPORTED_TAGS.contains(&tag.name.as_str()) && !unsupported.contains(&tag.name.as_str())
+        {
+            unsupported.push(&tag.name);
+        }
+    }
+    if !unsupported.is_empty() {
+        let unsupported = unsupported
+            .iter()
+            .map(|x| format!("<code>&lt;{}&gt;</code>", escape_html(x)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(Error::Custom(
+            Rule::UnsupportedHtmlTag,
+            format!("{field} uses {unsupported}, which most apps strip or show as text."),
+        ));
+    }
+
+    let mut relative: Vec<&str> = Vec::new();
+    for tag in &tags {
+        for (attr, value) in &tag.attributes {
+            let is_link = attr == "href" || attr == "src";
+            if is_link
+                && Url::parse(value) == Err(url::ParseError::RelativeUrlWithoutBase)
+                && !relative.contains(&value.as_str())
+            {
+                relative.push(value);
+            }
+        }
+    }
+    if !relative.is_empty() {
+        let relative = relative
+            .iter()
+            .map(|x| format!("“<code>{}</code>”", escape_html(x)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(Error::Custom(
+            Rule::RelativeHtmlLink,
+            format!("{field} links to {relative}. Apps do not know what the links are relative to, so use absolute URLs."),
+        ));
+    }
+
+    let length = html.chars().count();
+    if length > MAX_LENGTH {
+        errors.push(Error::Custom(
+            Rule::DescriptionTooLong,
+            format!(
+                "{field} is {length} characters long, so Apple Podcasts cuts it off after {MAX_LENGTH} characters.",
+            ),
+        ));
+    }
+
+    errors
+}
+
+/// Text of the element, with the CDATA sections unwrapped and the rest unescaped.
+fn text(raw: &str) -> String {
+    let mut text = String::new();
+    let mut rest = raw;
+    while let Some(start) = rest.find("<![CDATA[") {
+        text.push_str(&decode_entities(&rest[..start]));
+        let cdata = &rest[start + "<![CDATA[".len()..];
+        let end = cdata.find("]]>").unwrap_or(cdata.len());
+        text.push_str(&cdata[..end]);
+        rest = &cdata[(end + "]]>".len()).min(cdata.len())..];
+    }
+    text.push_str(&decode_entities(rest));
+    text
+}
+
+struct Tag {
+    /// In lowercase.
+    name: String,
+    is_closing: bool,
+    is_self_closing: bool,
+    /// Names are in lowercase.
+    attributes: Vec<(String, String)>,
+}
+
+/// Scans HTML for tags, skipping comments and any “<” that does not start a tag.
+fn tags(html: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut i = 0;
+
+    while let Some(start) = html[i..].find('<').map(|j| i + j) {
+        let rest = &html[start + 1..];
+        if rest.starts_with("!--") {
+            i = html[start..]
+                .find("-->")
+                .map_or(html.len(), |j| start + j + 3);
+            continue;
+        }
+        let is_closing = rest.starts_with('/');
+        let rest = rest.trim_start_matches('/');
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            i = start + 1;
+            continue;
+        }
+
+        // Quoted attribute values may contain “>”.
+        let mut quote = None;
+        let end = rest.char_indices().find_map(|(j, c)| {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '>') => return Some(j),
+                _ => {}
+            }
+            None
+        });
+        let end = match end {
+            Some(end) => end,
+            None => break,
+        };
+        let inside = &rest[..end];
+        i = html.len() - rest.len() + end + 1;
+
+        let name_end = inside
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(inside.len());
+        tags.push(Tag {
+            name: inside[..name_end].to_lowercase(),
+            is_closing,
+            is_self_closing: inside.ends_with('/'),
+            attributes: attributes(&inside[name_end..]),
+        });
+    }
+
+    tags
+}
+
+fn attributes(s: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = s.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_lowercase();
+        rest = rest[name_end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, remainder) = match after.chars().next() {
+                    Some(q @ ('"' | '\'')) => {
+                        let inner = &after[1..];
+                        let end = inner.find(q).unwrap_or(inner.len());
+                        (&inner[..end], &inner[(end + 1).min(inner.len())..])
+                    }
+                    _ => {
+                        let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                        (&after[..end], &after[end..])
+                    }
+                };
+                rest = remainder;
+                decode_entities(value)
+            }
+            None => String::new(),
+        };
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+    }
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(name: &str, raw: &str, has_elements: bool) -> Vec<Rule> {
+        analyze_html(name, raw, has_elements)
+            .into_iter()
+            .map(|error| match error {
+                Error::Custom(rule, _) => rule,
+                _ => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze_html() {
+        assert!(rules(
+            "description",
+            "<![CDATA[<p>Notes with <a href='https://example.com/?a=1&b=2'>a link</a><p>and<br>more]]>",
+            false,
+        )
+        .is_empty());
+        assert!(rules(
+            "description",
+            "&lt;ul&gt;&lt;li&gt;One&lt;li&gt;Two&lt;/ul&gt; 1 &lt; 2",
+            false
+        )
+        .is_empty());
+
+        assert_eq!(
+            rules(
+                "content:encoded",
+                "<![CDATA[<div><b>Bold <i>both</b> &lt;p&gt; <a href=\"/episodes/1\">]]>",
+                false,
+            ),
+            [
+                Rule::DoubleEscapedHtml,
+                Rule::UnbalancedHtml,
+                Rule::UnsupportedHtmlTag,
+                Rule::RelativeHtmlLink,
+            ]
+        );
+        assert_eq!(
+            rules("itunes:summary", &"<p>Long</p>".repeat(400), true),
+            [Rule::HtmlOutsideCdata, Rule::DescriptionTooLong]
+        );
+        // The limit is in characters, not bytes.
+        assert!(rules("description", &"é".repeat(MAX_LENGTH), false).is_empty());
+        assert_eq!(
+            rules("description", &"é".repeat(MAX_LENGTH + 1), false),
+            [Rule::DescriptionTooLong]
+        );
+    }
+
+    #[test]
+    fn test_analyze_html_escapes_links() {
+        let errors = analyze_html(
+            "description",
+            "<![CDATA[<a href='<img src=x onerror=alert(1)>'>x</a>]]>",
+            false,
+        );
+        assert_eq!(
+            errors,
+            [Error::Custom(
+                Rule::RelativeHtmlLink,
+                "<code>&lt;description&gt;</code> links to “<code>&lt;img src=x onerror=alert(1)&gt;</code>”. Apps do not know what the links are relative to, so use absolute URLs.".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Positions of elements in the feed's source.
+//!
+//! badpod does not keep track of where elements come from, so the source is scanned separately
+//! and the resulting tree is matched with the analysis tree.
+
+use super::{Node, NODE_VALUE};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Canonical prefixes of the namespaces that the validator knows about.
+///
+/// The first URI of each prefix is the one recommended by the specification.
+pub(super) const KNOWN_NAMESPACES: [(&str, &str); 5] = [
+    ("podcast", "https://podcastindex.org/namespace/1.0"),
+    ("podcast", "http://podcastindex.org/namespace/1.0"),
+    ("itunes", "http://www.itunes.com/dtds/podcast-1.0.dtd"),
+    ("itunes", "https://www.itunes.com/dtds/podcast-1.0.dtd"),
+    ("atom", "http://www.w3.org/2005/Atom"),
+];
+
+/// Region of the source.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Span {
+    /// Byte offset of the first character.
+    pub start: usize,
+    /// Byte offset after the last character.
+    pub end: usize,
+    /// Line of the first character, starting from 1.
+    pub line: usize,
+    /// Column of the first character, starting from 1.
+    pub column: usize,
+    /// Line of the last character, starting from 1.
+    pub end_line: usize,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Attribute {
+    pub name: String,
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Element {
+    /// Qualified name, with the prefixes of known namespaces replaced by their canonical ones.
+    pub name: String,
+    /// URI that the prefix of the name is bound to, if the name has a declared prefix.
+    pub namespace: Option<String>,
+    /// From the start of the opening tag to the end of the closing tag.
+    pub span: Span,
+    pub attributes: Vec<Attribute>,
+    /// Everything between the opening and the closing tags.
+    pub content: Option<Span>,
+    pub children: Vec<Element>,
+}
+
+/// Bytes between the offsets at which [`LineIndex`] records the number of preceding characters.
+const CHECKPOINT_INTERVAL: usize = 64;
+
+/// Maps byte offsets to lines and columns.
+pub(super) struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    /// Offsets of characters and the number of characters before them, so that columns can be
+    /// counted without going back to the start of the line, which may be the whole feed.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub(super) fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let mut checkpoints = vec![(0, 0)];
+        let mut last_checkpoint = 0;
+        for (count, (i, c)) in source.char_indices().enumerate() {
+            if i >= last_checkpoint + CHECKPOINT_INTERVAL {
+                checkpoints.push((i, count));
+                last_checkpoint = i;
+            }
+            if c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        LineIndex {
+            source,
+            line_starts,
+            checkpoints,
+        }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        }
+    }
+
+    /// Number of characters before the byte offset.
+    fn chars_before(&self, offset: usize) -> usize {
+        let i = self.checkpoints.partition_point(|(o, _)| *o <= offset) - 1;
+        let (checkpoint, count) = self.checkpoints[i];
+        count + self.source[checkpoint..offset].chars().count()
+    }
+
+    pub(super) fn span(&self, start: usize, end: usize) -> Span {
+        let line = self.line(start);
+        let line_start = self.line_starts[line - 1];
+        Span {
+            start,
+            end,
+            line,
+            column: self.chars_before(start) - self.chars_before(line_start) + 1,
+            end_line: self.line(end.saturating_sub(1).max(start)),
+        }
+    }
+}
+
+struct OpenElement {
+    raw_name: String,
+    start: usize,
+    content_start: usize,
+    attributes: Vec<Attribute>,
+    namespaces: Vec<(String, String)>,
+    children: Vec<Element>,
+}
+
+/// Scans the source for elements and returns the root one.
+///
+/// The scanner is lenient: unclosed elements are closed at the end of the source, and closing
+/// tags without a matching opening tag are ignored.
+pub fn parse(source: &str) -> Option<Element> {
+    let index = LineIndex::new(source);
+    let mut stack: Vec<OpenElement> = Vec::new();
+    let mut root = None;
+    let mut i = 0;
+
+    let find = |from: usize, pattern: &str| source[from..].find(pattern).map(|j| from + j);
+
+    while let Some(start) = find(i, "<") {
+        let rest = &source[start..];
+        if rest.starts_with("<!--") {
+            i = find(start, "-->").map_or(source.len(), |j| j + 3);
+        } else if rest.starts_with("<![CDATA[") {
+            i = find(start, "]]>").map_or(source.len(), |j| j + 3);
+        } else if rest.starts_with("<?") {
+            i = find(start, "?>").map_or(source.len(), |j| j + 2);
+        } else if rest.starts_with("<!") {
+            i = find(start, ">").map_or(source.len(), |j| j + 1);
+        } else if rest.starts_with("</") {
+            let end = find(start, ">").map_or(source.len(), |j| j + 1);
+            let raw_name = source[start + 2..end].trim_end_matches('>').trim();
+            if let Some(pos) = stack.iter().rposition(|e| e.raw_name == raw_name) {
+                while stack.len() > pos {
+                    let open = stack.pop().unwrap();
+                    let is_matching = stack.len() == pos;
+                    let (content_end, end) = if is_matching {
+                        (start, end)
+                    } else {
+                        (start, start)
+                    };
+                    let element = close(&index, &stack, open, content_end, end);
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => root = Some(element),
+                    }
+                }
+            }
+            i = end;
+        } else {
+            let (open, end, self_closing) = scan_start_tag(source, &index, start);
+            i = end;
+            if self_closing {
+                let element = close(&index, &stack, open, end, end);
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            } else {
+                stack.push(open);
+            }
+        }
+    }
+
+    while let Some(open) = stack.pop() {
+        let element = close(&index, &stack, open, source.len(), source.len());
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => root = Some(element),
+        }
+    }
+
+    root
+}
+
+fn scan_start_tag(source: &str, index: &LineIndex, start: usize) -> (OpenElement, usize, bool) {
+    let bytes = source.as_bytes();
+    let is_name_end = |b: u8| b.is_ascii_whitespace() || b == b'/' || b == b'>' || b == b'=';
+
+    let mut i = start + 1;
+    while i < bytes.len() && !is_name_end(bytes[i]) {
+        i += 1;
+    }
+    let raw_name = source[start + 1..i].to_string();
+
+    let mut attributes = Vec::new();
+    let mut namespaces = Vec::new();
+    let mut self_closing = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => {
+                i += 1;
+                break;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                self_closing = true;
+                i += 2;
+                break;
+            }
+            b if b.is_ascii_whitespace() || b == b'/' => i += 1,
+            _ => {
+                let attr_start = i;
+                while i < bytes.len() && !is_name_end(bytes[i]) {
+                    i += 1;
+                }
+                let name = source[attr_start..i].to_string();
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if bytes.get(i) != Some(&b'=') {
+                    continue;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                let value = match bytes.get(i) {
+                    Some(&quote) if quote == b'"' || quote == b'\'' => {
+                        let value_start = i + 1;
+                        let value_end = source[value_start..]
+                            .find(quote as char)
+                            .map_or(source.len(), |j| value_start + j);
+                        i = (value_end + 1).min(source.len());
+                        source[value_start..value_end].to_string()
+                    }
+                    _ => continue,
+                };
+                if let Some(prefix) = name.strip_prefix("xmlns:") {
+                    namespaces.push((prefix.to_string(), value.clone()));
+                }
+                attributes.push(Attribute {
+                    name,
+                    value,
+                    span: index.span(attr_start, i),
+                });
+            }
+        }
+    }
+
+    let open = OpenElement {
+        raw_name,
+        start,
+        content_start: i,
+        attributes,
+        namespaces,
+        children: Vec::new(),
+    };
+    (open, i, self_closing)
+}
+
+fn close(
+    index: &LineIndex,
+    stack: &[OpenElement],
+    open: OpenElement,
+    content_end: usize,
+    end: usize,
+) -> Element {
+    let namespace = open.raw_name.split_once(':').and_then(|(prefix, _)| {
+        std::iter::once(&open)
+            .chain(stack.iter().rev())
+            .flat_map(|e| e.namespaces.iter())
+            .find(|(p, _)| p == prefix)
+            .map(|(_, uri)| uri.clone())
+    });
+    let name = canonical_name(&open.raw_name, namespace.as_deref());
+
+    let content = if content_end > open.content_start {
+        Some(index.span(open.content_start, content_end))
+    } else {
+        None
+    };
+
+    Element {
+        name,
+        namespace,
+        span: index.span(open.start, end.max(open.content_start)),
+        attributes: open.attributes,
+        content,
+        children: open.children,
+    }
+}
+
+fn canonical_name(raw_name: &str, namespace: Option<&str>) -> String {
+    if let (Some((_, local_name)), Some(known_prefix)) =
+        (raw_name.split_once(':'), namespace.and_then(known_prefix))
+    {
+        return format!("{known_prefix}:{local_name}");
+    }
+    raw_name.to_string()
+}
+
+/// Returns the canonical prefix of a namespace URI that the validator knows about.
+pub fn known_prefix(uri: &str) -> Option<&'static str> {
+    KNOWN_NAMESPACES
+        .iter()
+        .find(|(_, known_uri)| *known_uri == uri)
+        .map(|(known_prefix, _)| *known_prefix)
+}
+
+/// Copies the positions of the elements to the corresponding nodes.
+///
+/// Nodes are matched to elements by their name and by their order among the siblings with the
+/// same name.
+pub fn attach_spans(node: &mut Node, element: &Element) {
+    node.span = Some(element.span);
+    node.attribute_spans = element
+        .attributes
+        .iter()
+        .map(|attr| (attr.name.clone(), attr.span))
+        .collect();
+    if let Some(content) = element.content {
+        node.attribute_spans.push((NODE_VALUE.to_string(), content));
+    }
+
+    // The n-th child node of a name comes from the n-th child element of that name.
+    let mut by_name: HashMap<&str, Vec<&Element>> = HashMap::new();
+    for child_element in &element.children {
+        by_name
+            .entry(&child_element.name)
+            .or_default()
+            .push(child_element);
+    }
+    let mut by_name: HashMap<&str, std::vec::IntoIter<&Element>> = by_name
+        .into_iter()
+        .map(|(name, elements)| (name, elements.into_iter()))
+        .collect();
+    for child in &mut node.children {
+        let name = child.name.to_string();
+        if let Some(child_element) = by_name.get_mut(name.as_str()).and_then(Iterator::next) {
+            attach_spans(child, child_element);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let source = "<?xml version=\"1.0\"?>\n<rss xmlns:pi=\"https://podcastindex.org/namespace/1.0\">\n  <channel>\n    <pi:locked owner=\"a@b.c\">yes</pi:locked>\n    <!-- <item> -->\n    <item/>\n  </channel>\n</rss>";
+        let rss = parse(source).unwrap();
+        assert_eq!(rss.name, "rss");
+        assert_eq!(rss.span.line, 2);
+        assert_eq!(rss.span.end_line, 8);
+
+        let channel = &rss.children[0];
+        assert_eq!(channel.children.len(), 2);
+
+        let locked = &channel.children[0];
+        assert_eq!(locked.name, "podcast:locked");
+        assert_eq!(
+            locked.namespace.as_deref(),
+            Some("https://podcastindex.org/namespace/1.0")
+        );
+        assert_eq!((locked.span.line, locked.span.column), (4, 5));
+        assert_eq!(locked.attributes[0].name, "owner");
+        assert_eq!(locked.attributes[0].value, "a@b.c");
+        assert_eq!(locked.attributes[0].span.column, 16);
+        assert_eq!(
+            &source[locked.content.unwrap().start..locked.content.unwrap().end],
+            "yes"
+        );
+
+        assert_eq!(channel.children[1].name, "item");
+        assert_eq!(channel.children[1].content, None);
+    }
+
+    #[test]
+    fn test_line_index() {
+        let source = format!("<a>\n{}\n<b/>", "é<c/>".repeat(100));
+        let index = LineIndex::new(&source);
+        for (offset, _) in source.char_indices().rev() {
+            let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+            assert_eq!(
+                index.span(offset, offset).column,
+                source[line_start..offset].chars().count() + 1
+            );
+        }
+        assert_eq!(index.span(source.len() - 4, source.len()).line, 3);
+    }
+}
@@ -0,0 +1,169 @@
+//! Overview of a feed and of the findings in it.
+
+use super::source::Span;
+use super::{Node, Report, Severity, NODE_VALUE};
+use chrono::{DateTime, FixedOffset};
+
+/// Podcast namespace elements whose adoption among the items is summarized.
+const FEATURES: [(&str, &str); 4] = [
+    ("transcripts", "podcast:transcript"),
+    ("chapters", "podcast:chapters"),
+    ("persons", "podcast:person"),
+    ("value blocks", "podcast:value"),
+];
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Summary {
+    pub items: usize,
+    pub live_items: usize,
+    /// Size of the source in bytes.
+    pub size: usize,
+    /// Publication dates of the oldest and the newest item.
+    pub date_range: Option<(DateTime<FixedOffset>, DateTime<FixedOffset>)>,
+    /// Number of findings in the elements of each name, most first.
+    pub findings_by_tag: Vec<(String, usize)>,
+    /// Number of items that have each feature, e.g. `("transcripts", 12)`.
+    pub features: Vec<(&'static str, usize)>,
+    /// Where the first error is in the source, if it is known.
+    pub first_error: Option<Span>,
+}
+
+pub fn summarize(report: &Report, source: &str) -> Summary {
+    let channel = report
+        .root
+        .children
+        .iter()
+        .find(|n| n.name.to_string() == "channel");
+    let children = |name: &'static str| {
+        channel
+            .into_iter()
+            .flat_map(|c| c.children.iter())
+            .filter(move |n| n.name.to_string() == name)
+    };
+
+    let items: Vec<&Node> = children("item").collect();
+    let dates: Vec<DateTime<FixedOffset>> = items
+        .iter()
+        .flat_map(|item| item.children.iter())
+        .filter(|n| n.name.to_string() == "pubDate")
+        .filter_map(|n| {
+            let (_, span) = n
+                .attribute_spans
+                .iter()
+                .find(|(name, _)| name == NODE_VALUE)?;
+            DateTime::parse_from_rfc2822(source[span.start..span.end].trim()).ok()
+        })
+        .collect();
+    let date_range = match (dates.iter().min(), dates.iter().max()) {
+        (Some(first), Some(last)) => Some((*first, *last)),
+        _ => None,
+    };
+
+    let features = FEATURES
+        .iter()
+        .map(|(label, name)| {
+            let count = items
+                .iter()
+                .filter(|item| item.children.iter().any(|n| n.name.to_string() == *name))
+                .count();
+            (*label, count)
+        })
+        .collect();
+
+    let mut findings_by_tag = Vec::new();
+    count_findings(&report.root, &mut findings_by_tag);
+    findings_by_tag.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then(a_name.cmp(b_name)));
+
+    Summary {
+        items: items.len(),
+        live_items: children("podcast:liveItem").count(),
+        size: source.len(),
+        date_range,
+        findings_by_tag,
+        features,
+        first_error: first_error(&report.root),
+    }
+}
+
+fn count_findings(node: &Node, counts: &mut Vec<(String, usize)>) {
+    if !node.errors.is_empty() {
+        let name = node.name.to_string();
+        match counts.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += node.errors.len(),
+            None => counts.push((name, node.errors.len())),
+        }
+    }
+    for child in &node.children {
+        count_findings(child, counts);
+    }
+}
+
+/// Finds the first error in the order in which the tree is shown.
+fn first_error(node: &Node) -> Option<Span> {
+    node.errors
+        .iter()
+        .find(|error| error.severity() == Severity::Error)
+        .and_then(|error| node.error_span(error))
+        .or_else(|| node.children.iter().find_map(first_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rules::Rule;
+    use super::super::{source, Error, Namespace, TagName};
+    use super::*;
+
+    fn node(name: TagName, children: Vec<Node>, errors: Vec<Error>) -> Node {
+        Node {
+            name,
+            children,
+            errors,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let source = "<rss><channel><item><pubDate>Tue, 10 Jan 2023 08:00:00 GMT</pubDate><podcast:transcript/></item><item><pubDate>Sat, 07 Sep 2002 09:42:31 GMT</pubDate></item></channel></rss>";
+        let tag = |name: &str| TagName(None, name.to_string());
+        let pub_date = || node(tag("pubDate"), Vec::new(), Vec::new());
+        let missing_guid = || Error::Custom(Rule::ItemMissingGuid, String::new());
+        let transcript = node(
+            TagName(Some(Namespace::Podcast), "transcript".to_string()),
+            Vec::new(),
+            vec![Error::MissingAttribute("url".to_string())],
+        );
+        let mut root = node(
+            tag("rss"),
+            vec![node(
+                tag("channel"),
+                vec![
+                    node(
+                        tag("item"),
+                        vec![pub_date(), transcript],
+                        vec![missing_guid()],
+                    ),
+                    node(tag("item"), vec![pub_date()], vec![missing_guid()]),
+                ],
+                Vec::new(),
+            )],
+            Vec::new(),
+        );
+        source::attach_spans(&mut root, &source::parse(source).unwrap());
+        let summary = summarize(&Report { root }, source);
+
+        assert_eq!((summary.items, summary.live_items), (2, 0));
+        let (first, last) = summary.date_range.unwrap();
+        assert_eq!(first.format("%Y").to_string(), "2002");
+        assert_eq!(last.format("%Y").to_string(), "2023");
+        assert_eq!(summary.features[0], ("transcripts", 1));
+        assert_eq!(
+            summary.findings_by_tag,
+            [
+                ("item".to_string(), 2),
+                ("podcast:transcript".to_string(), 1)
+            ]
+        );
+        assert_eq!(summary.first_error.map(|span| span.start), Some(68));
+    }
+}
@@ -0,0 +1,555 @@
+//! Encoding and well-formedness of the feed's source, checked before it is parsed.
+//!
+//! badpod gives up at the first syntax error, so these checks look for the problems that
+//! typically break feeds and point to each of them in the source.
+
+use super::source::{LineIndex, Span};
+use super::{escape_html, Error, Rule};
+use serde::{Deserialize, Serialize};
+
+/// Occurrences of a rule to report before summarizing the rest.
+const MAX_REPORTED: usize = 10;
+
+/// Entities that XML defines without a DTD.
+const PREDEFINED_ENTITIES: [&str; 5] = ["amp", "lt", "gt", "quot", "apos"];
+
+/// HTML entities that are often used in feeds, with their code points.
+const HTML_ENTITIES: [(&str, u32); 12] = [
+    ("nbsp", 160),
+    ("copy", 169),
+    ("reg", 174),
+    ("eacute", 233),
+    ("ndash", 8211),
+    ("mdash", 8212),
+    ("lsquo", 8216),
+    ("rsquo", 8217),
+    ("ldquo", 8220),
+    ("rdquo", 8221),
+    ("hellip", 8230),
+    ("trade", 8482),
+];
+
+/// Problem in the source and where it is.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub error: Error,
+    pub span: Span,
+}
+
+/// Decodes the feed and checks its source.
+///
+/// The feed is decoded as UTF-8, or as UTF-16 if it starts with a UTF-16 byte order mark. The
+/// byte order mark is dropped, and bytes that cannot be decoded are replaced with “�”.
+pub fn analyze(bytes: &[u8]) -> (String, Vec<Diagnostic>) {
+    let (source, mut diagnostics) = decode(bytes);
+    diagnostics.extend(diagnose(&source));
+    (source, diagnostics)
+}
+
+/// Excerpt of the line around the span and the column of the span within it, in characters.
+pub fn excerpt(source: &str, span: &Span) -> (String, usize) {
+    const WIDTH: usize = 40;
+
+    let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[span.start..]
+        .find(['\r', '\n'])
+        .map_or(source.len(), |i| span.start + i);
+    let before: Vec<char> = source[line_start..span.start].chars().collect();
+    let after = &source[span.start..line_end];
+
+    let skipped = before.len().saturating_sub(WIDTH);
+    let mut excerpt = String::new();
+    if skipped > 0 {
+        excerpt.push('…');
+    }
+    excerpt.extend(&before[skipped..]);
+    let column = excerpt.chars().count();
+    excerpt.extend(after.chars().take(WIDTH));
+    if after.chars().count() > WIDTH {
+        excerpt.push('…');
+    }
+    (excerpt, column)
+}
+
+fn decode(bytes: &[u8]) -> (String, Vec<Diagnostic>) {
+    let utf16 = if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        Some((rest, u16::from_le_bytes as fn([u8; 2]) -> u16))
+    } else {
+        bytes
+            .strip_prefix(b"\xFE\xFF")
+            .map(|rest| (rest, u16::from_be_bytes as fn([u8; 2]) -> u16))
+    };
+    let utf8 = bytes.strip_prefix(b"\xEF\xBB\xBF");
+    let (source, invalid_byte) = match (utf16, utf8) {
+        (Some((rest, from_bytes)), _) => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|pair| from_bytes([pair[0], pair[1]]))
+                .collect();
+            (String::from_utf16_lossy(&units), None)
+        }
+        (None, rest) => {
+            let rest = rest.unwrap_or(bytes);
+            let invalid_byte = std::str::from_utf8(rest)
+                .err()
+                .map(|e| (e.valid_up_to(), rest[e.valid_up_to()]));
+            (String::from_utf8_lossy(rest).into_owned(), invalid_byte)
+        }
+    };
+
+    let index = LineIndex::new(&source);
+    let mut diagnostics = Vec::new();
+    let (declared, declaration_span) = match declared_encoding(&source) {
+        Some((name, start)) => (Some(name), index.span(start, start + name.len())),
+        None => (None, index.span(0, 0)),
+    };
+    let is_declared_utf8 = match declared {
+        Some(name) => name.eq_ignore_ascii_case("utf-8"),
+        None => true,
+    };
+    let is_declared_utf16 = declared.is_some_and(|name| name.to_lowercase().starts_with("utf-16"));
+
+    if utf8.is_some() {
+        diagnostics.push(Diagnostic {
+            error: Error::Custom(
+                Rule::ByteOrderMark,
+                "The feed starts with a UTF-8 byte order mark. XML allows it, but some apps fail to read feeds that have one.".to_string(),
+            ),
+            span: index.span(0, 0),
+        });
+    }
+
+    match (utf16, invalid_byte, declared) {
+        (Some(_), _, _) => {
+            if let (Some(name), false) = (declared, is_declared_utf16) {
+                diagnostics.push(Diagnostic {
+                    error: Error::Custom(
+                        Rule::EncodingMismatch,
+                        format!(
+                            "The feed is declared as {}, but it starts with a UTF-16 byte order mark.",
+                            escape_html(name)
+                        ),
+                    ),
+                    span: declaration_span,
+                });
+            }
+            diagnostics.push(Diagnostic {
+                error: Error::Custom(
+                    Rule::NonUtf8Encoding,
+                    "The feed is encoded in UTF-16. XML allows it, but many apps only read feeds encoded in UTF-8.".to_string(),
+                ),
+                span: declaration_span,
+            });
+        }
+        (None, Some((offset, byte)), _) if is_declared_utf8 => {
+            let declaration = match declared {
+                Some(_) => "is declared as UTF-8",
+                None => "has no encoding declaration, so it is read as UTF-8",
+            };
+            diagnostics.push(Diagnostic {
+                error: Error::Custom(
+                    Rule::EncodingMismatch,
+                    format!(
+                        "The feed {declaration}, but byte 0x{byte:02X} is not valid UTF-8. The feed is probably encoded in Windows-1252 or ISO-8859-1, so convert it to UTF-8."
+                    ),
+                ),
+                span: index.span(offset, offset + '\u{FFFD}'.len_utf8()),
+            });
+        }
+        (None, Some(_), Some(name)) => diagnostics.push(Diagnostic {
+            error: Error::Custom(
+                Rule::NonUtf8Encoding,
+                format!(
+                    "The feed is encoded in {}. XML allows it, but many apps only read feeds encoded in UTF-8, and the characters that are not valid UTF-8 are shown here as “�”.",
+                    escape_html(name)
+                ),
+            ),
+            span: declaration_span,
+        }),
+        (None, None, Some(name)) if is_declared_utf16 => diagnostics.push(Diagnostic {
+            error: Error::Custom(
+                Rule::EncodingMismatch,
+                format!(
+                    "The feed is declared as {}, but it is encoded in UTF-8. Declare the encoding as UTF-8.",
+                    escape_html(name)
+                ),
+            ),
+            span: declaration_span,
+        }),
+        (None, None, Some(name)) if !is_declared_utf8 => {
+            // ASCII reads the same in the other common encodings.
+            if let Some((offset, c)) = source.char_indices().find(|(_, c)| !c.is_ascii()) {
+                let misread: String = c.to_string().bytes().map(char::from).collect();
+                diagnostics.push(Diagnostic {
+                    error: Error::Custom(
+                        Rule::EncodingMismatch,
+                        format!(
+                            "The feed is declared as {}, but it is encoded in UTF-8, so apps read “{c}” as “{misread}”. Declare the encoding as UTF-8.",
+                            escape_html(name)
+                        ),
+                    ),
+                    span: index.span(offset, offset + c.len_utf8()),
+                });
+            }
+        }
+        _ => {}
+    }
+
+    (source, diagnostics)
+}
+
+/// Value of `encoding` in the XML declaration and its offset.
+fn declared_encoding(source: &str) -> Option<(&str, usize)> {
+    let declaration = &source[..source.find("?>")?];
+    if !declaration.starts_with("<?xml") {
+        return None;
+    }
+    let attr = declaration.find("encoding")? + "encoding".len();
+    let rest = declaration[attr..]
+        .trim_start()
+        .strip_prefix('=')?
+        .trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &rest[1..];
+    let value = &value[..value.find(quote)?];
+    let start = declaration.len() - rest.len() + 1;
+    Some((value, start))
+}
+
+/// Checks the characters, references and tags of already decoded source.
+pub fn diagnose(source: &str) -> Vec<Diagnostic> {
+    let index = LineIndex::new(source);
+    let mut found: Vec<(Rule, String, usize, usize)> = Vec::new();
+
+    for (i, c) in source.char_indices() {
+        if !is_allowed(c) {
+            found.push((
+                Rule::InvalidXmlCharacter,
+                format!(
+                    "Character U+{:04X} is not allowed in XML, not even escaped. Remove it.",
+                    c as u32
+                ),
+                i,
+                i + c.len_utf8(),
+            ));
+        }
+    }
+
+    let bytes = source.as_bytes();
+    let find = |from: usize, pattern: &str| source[from..].find(pattern).map(|j| from + j);
+    // Names and positions of the opening tags that have not been closed yet.
+    let mut stack: Vec<(&str, usize, usize)> = Vec::new();
+    let mut i = 0;
+
+    while let Some(start) = source[i..].find(['<', '&']).map(|j| i + j) {
+        if bytes[start] == b'&' {
+            found.extend(check_reference(source, start));
+            i = start + 1;
+            continue;
+        }
+
+        let rest = &source[start..];
+        let terminator = if rest.starts_with("<!--") {
+            Some(("-->", "comment"))
+        } else if rest.starts_with("<![CDATA[") {
+            Some(("]]>", "CDATA section"))
+        } else if rest.starts_with("<?") {
+            Some(("?>", "processing instruction"))
+        } else if rest.starts_with("<!") {
+            Some((">", "declaration"))
+        } else {
+            None
+        };
+        if let Some((terminator, what)) = terminator {
+            match find(start + 2, terminator) {
+                Some(end) => i = end + terminator.len(),
+                None => {
+                    found.push((
+                        Rule::UnclosedTag,
+                        format!(
+                            "The {what} is never closed with <code>{}</code>.",
+                            terminator.replace('>', "&gt;")
+                        ),
+                        start,
+                        (start + 4).min(source.len()),
+                    ));
+                    i = source.len();
+                }
+            }
+            continue;
+        }
+
+        let is_closing = rest.starts_with("</");
+        let name_start = start + if is_closing { 2 } else { 1 };
+        let name_end = source[name_start..]
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .map_or(source.len(), |j| name_start + j);
+        let name = &source[name_start..name_end];
+        if !name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == ':') {
+            found.push((
+                Rule::UnescapedCharacter,
+                "Less-than sign is not escaped. Write it as <code>&amp;lt;</code>.".to_string(),
+                start,
+                start + 1,
+            ));
+            i = start + 1;
+            continue;
+        }
+
+        // Quoted attribute values may contain “>”.
+        let mut end = None;
+        let mut quote = None;
+        for (j, b) in bytes.iter().enumerate().skip(name_end) {
+            match (quote, *b) {
+                (Some(q), b) if b == q => quote = None,
+                (Some(_), b'&') => found.extend(check_reference(source, j)),
+                (Some(_), _) => {}
+                (None, b'"' | b'\'') => quote = Some(*b),
+                (None, b'>') => {
+                    end = Some(j + 1);
+                    break;
+                }
+                (None, b'<') => break,
+                (None, _) => {}
+            }
+        }
+        let end = match end {
+            Some(end) => end,
+            None => {
+                found.push((
+                    Rule::UnclosedTag,
+                    format!(
+                        "The tag <code>&lt;{}{}</code> is never closed with <code>&gt;</code>.",
+                        if is_closing { "/" } else { "" },
+                        escape_html(name)
+                    ),
+                    start,
+                    name_end,
+                ));
+                i = name_end;
+                continue;
+            }
+        };
+        i = end;
+
+        if !is_closing {
+            if !source[..end].ends_with("/>") {
+                stack.push((name, start, end));
+            }
+            continue;
+        }
+        match stack.iter().rposition(|(open, _, _)| *open == name) {
+            Some(pos) => {
+                let line = index.span(start, end).line;
+                for (open, open_start, open_end) in stack.drain(pos..).skip(1) {
+                    found.push((
+                        Rule::UnclosedTag,
+                        format!(
+                            "<code>&lt;{}&gt;</code> is not closed before <code>&lt;/{}&gt;</code> on line {line}.",
+                            escape_html(open),
+                            escape_html(name)
+                        ),
+                        open_start,
+                        open_end,
+                    ));
+                }
+            }
+            None => found.push((
+                Rule::UnclosedTag,
+                format!(
+                    "<code>&lt;/{}&gt;</code> has no matching opening tag.",
+                    escape_html(name)
+                ),
+                start,
+                end,
+            )),
+        }
+    }
+
+    for (open, start, end) in stack {
+        found.push((
+            Rule::UnclosedTag,
+            format!(
+                "<code>&lt;{}&gt;</code> is never closed.",
+                escape_html(open)
+            ),
+            start,
+            end,
+        ));
+    }
+
+    found.sort_by_key(|(_, _, start, _)| *start);
+    let mut counts: Vec<(Rule, usize)> = Vec::new();
+    for (rule, _, _, _) in &found {
+        match counts.iter_mut().find(|(r, _)| r == rule) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*rule, 1)),
+        }
+    }
+    let mut diagnostics = Vec::new();
+    let mut reported: Vec<(Rule, usize)> = Vec::new();
+    for (rule, mut message, start, end) in found {
+        let n = match reported.iter_mut().find(|(r, _)| *r == rule) {
+            Some((_, n)) => {
+                *n += 1;
+                *n
+            }
+            None => {
+                reported.push((rule, 1));
+                1
+            }
+        };
+        if n > MAX_REPORTED {
+            continue;
+        }
+        let total = counts
+            .iter()
+            .find(|(r, _)| *r == rule)
+            .map_or(0, |(_, c)| *c);
+        if n == MAX_REPORTED && total > MAX_REPORTED {
+            message.push_str(&format!(
+                " There are {} more problems like this one.",
+                total - MAX_REPORTED
+            ));
+        }
+        diagnostics.push(Diagnostic {
+            error: Error::Custom(rule, message),
+            span: index.span(start, end),
+        });
+    }
+    diagnostics
+}
+
+/// Whether the character may appear in an XML 1.0 document.
+fn is_allowed(c: char) -> bool {
+    !matches!(c, '\0'..='\u{8}' | '\u{B}' | '\u{C}' | '\u{E}'..='\u{1F}' | '\u{FFFE}' | '\u{FFFF}')
+}
+
+/// Checks the entity or character reference that starts with the ampersand at `start`.
+fn check_reference(source: &str, start: usize) -> Option<(Rule, String, usize, usize)> {
+    let rest = &source[start + 1..];
+    let name_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '#' | '_' | '-' | '.' | ':')))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    if name.is_empty() || !rest[name_len..].starts_with(';') {
+        return Some((
+            Rule::UnescapedCharacter,
+            "Ampersand is not escaped. Write it as <code>&amp;amp;</code>.".to_string(),
+            start,
+            start + 1,
+        ));
+    }
+    let end = start + name_len + 2;
+
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix('x') {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => number.parse().ok(),
+        };
+        return match code.and_then(char::from_u32) {
+            Some(c) if is_allowed(c) => None,
+            _ => Some((
+                Rule::InvalidXmlCharacter,
+                format!(
+                    "<code>&amp;{name};</code> refers to a character that is not allowed in XML."
+                ),
+                start,
+                end,
+            )),
+        };
+    }
+    if PREDEFINED_ENTITIES.contains(&name) {
+        return None;
+    }
+    let hint = match HTML_ENTITIES.iter().find(|(entity, _)| *entity == name) {
+        Some((_, code)) => format!(" Use <code>&amp;#{code};</code> instead."),
+        None => " Escape the ampersand as <code>&amp;amp;</code>.".to_string(),
+    };
+    Some((
+        Rule::UnescapedCharacter,
+        format!("<code>&amp;{name};</code> is not defined in XML.{hint}"),
+        start,
+        end,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(bytes: &[u8]) -> Vec<(Rule, usize, usize)> {
+        analyze(bytes)
+            .1
+            .into_iter()
+            .map(|d| match d.error {
+                Error::Custom(rule, _) => (rule, d.span.line, d.span.column),
+                error => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_encoding() {
+        assert_eq!(
+            findings(b"\xEF\xBB\xBF<rss/>"),
+            [(Rule::ByteOrderMark, 1, 1)]
+        );
+        assert_eq!(
+            findings(b"<?xml version=\"1.0\"?>\n<rss>Caf\xE9</rss>"),
+            [(Rule::EncodingMismatch, 2, 9)]
+        );
+        assert_eq!(
+            findings("<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<rss>Café</rss>".as_bytes()),
+            [(Rule::EncodingMismatch, 2, 9)]
+        );
+        assert!(findings(b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><rss/>").is_empty());
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let source = "<rss>\n<channel>\n<title>A & B &nbsp;&amp;</title>\n<item><title>\u{8}</item>\n</channel>\n</rsss>";
+        let diagnostics = diagnose(source);
+        let found: Vec<(Rule, usize, usize)> = diagnostics
+            .iter()
+            .map(|d| match &d.error {
+                Error::Custom(rule, _) => (*rule, d.span.line, d.span.column),
+                error => panic!("unexpected finding {error:?}"),
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Rule::UnclosedTag, 1, 1),
+                (Rule::UnescapedCharacter, 3, 10),
+                (Rule::UnescapedCharacter, 3, 14),
+                (Rule::UnclosedTag, 4, 7),
+                (Rule::InvalidXmlCharacter, 4, 14),
+                (Rule::UnclosedTag, 6, 1),
+            ]
+        );
+        assert_eq!(
+            diagnostics[2].error.to_string(),
+            "&nbsp; is not defined in XML. Use &#160; instead."
+        );
+
+        let (excerpt, column) = excerpt(source, &diagnostics[1].span);
+        assert_eq!(excerpt, "<title>A & B &nbsp;&amp;</title>");
+        assert_eq!(column, 9);
+    }
+
+    #[test]
+    fn test_messages_escape_source() {
+        let message = |error: &Error| match error {
+            Error::Custom(_, message) => message.clone(),
+            error => panic!("unexpected finding {error:?}"),
+        };
+        let (_, diagnostics) =
+            analyze("<?xml version=\"1.0\" encoding=\"<b>\"?>\n<rss>Café</rss>".as_bytes());
+        assert!(message(&diagnostics[0].error).starts_with("The feed is declared as &lt;b&gt;,"));
+        assert_eq!(
+            message(&diagnose("<rss><b\"></rss>")[0].error),
+            "<code>&lt;b&quot;&gt;</code> is not closed before <code>&lt;/rss&gt;</code> on line 1."
+        );
+    }
+}
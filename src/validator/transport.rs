@@ -0,0 +1,215 @@
+//! Diagnostics of the HTTP response that delivered a feed.
+//!
+//! Browsers only expose some headers of cross-origin responses, so a header that is absent may
+//! just be hidden, see [`Response::is_exposed`].
+
+use super::{escape_html, Error, Rule};
+use serde::{Deserialize, Serialize};
+
+/// Headers that browsers always expose, see
+/// <https://developer.mozilla.org/en-US/docs/Glossary/CORS-safelisted_response_header>.
+const SAFELISTED_HEADERS: [&str; 7] = [
+    "cache-control",
+    "content-language",
+    "content-length",
+    "content-type",
+    "expires",
+    "last-modified",
+    "pragma",
+];
+
+/// Content types under which apps expect feeds.
+const FEED_CONTENT_TYPES: [&str; 5] = [
+    "application/rss+xml",
+    "application/xml",
+    "text/xml",
+    "application/atom+xml",
+    "application/x-rss+xml",
+];
+
+/// Responses smaller than this are not worth compressing.
+const MIN_COMPRESSIBLE_SIZE: usize = 1024;
+
+/// What the browser revealed about the response.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub status: u16,
+    /// Names are in lowercase.
+    pub headers: Vec<(String, String)>,
+    /// Size of the body after decompression, in bytes.
+    pub body_size: usize,
+    /// Time from sending the request until the whole body was received.
+    pub elapsed_ms: Option<f64>,
+    /// Whether the request went through the RSS Blue proxy, whose headers may differ.
+    pub via_proxy: bool,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the browser would show the header if the server sent it.
+    pub fn is_exposed(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        SAFELISTED_HEADERS.contains(&name.as_str())
+            || self
+                .header("access-control-expose-headers")
+                .is_some_and(|exposed| {
+                    exposed
+                        .split(',')
+                        .map(|x| x.trim())
+                        .any(|x| x == "*" || x.eq_ignore_ascii_case(&name))
+                })
+    }
+
+    /// MIME type of the `Content-Type` header, without parameters.
+    pub fn mime_type(&self) -> Option<String> {
+        let content_type = self.header("content-type")?;
+        let mime_type = content_type.split(';').next()?.trim().to_lowercase();
+        Some(mime_type)
+    }
+
+    /// `charset` parameter of the `Content-Type` header.
+    pub fn charset(&self) -> Option<&str> {
+        self.header("content-type")?
+            .split(';')
+            .skip(1)
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value.trim().trim_matches('"'))
+    }
+
+    /// Whether the body was compressed in transit, if that can be told.
+    pub fn is_compressed(&self) -> Option<bool> {
+        if let Some(encoding) = self.header("content-encoding") {
+            return Some(!encoding.trim().eq_ignore_ascii_case("identity"));
+        }
+        if self.is_exposed("content-encoding") {
+            return Some(false);
+        }
+        // Content-Length is the size before decompression.
+        let length: usize = self.header("content-length")?.trim().parse().ok()?;
+        Some(length < self.body_size)
+    }
+}
+
+/// Reports problems that make the feed cost more bandwidth or confuse apps.
+pub fn analyze(response: &Response) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    match response.mime_type() {
+        Some(mime_type) if FEED_CONTENT_TYPES.contains(&mime_type.as_str()) => {}
+        Some(mime_type) => errors.push(Error::Custom(
+            Rule::HttpContentType,
+            format!(
+                "The feed is served as <code>{}</code>. Some apps and directories reject feeds that are not served as XML, e.g. <code>application/rss+xml</code>.",
+                escape_html(&mime_type)
+            ),
+        )),
+        None => errors.push(Error::Custom(
+            Rule::HttpContentType,
+            "The response has no <code>Content-Type</code> header.".to_string(),
+        )),
+    }
+
+    if response.is_compressed() == Some(false) && response.body_size >= MIN_COMPRESSIBLE_SIZE {
+        errors.push(Error::Custom(
+            Rule::HttpUncompressed,
+            format!(
+                "The response of {} is not compressed. Feeds typically shrink several times with gzip or Brotli, and apps download them over and over.",
+                format_size(response.body_size)
+            ),
+        ));
+    }
+
+    let has_etag = response.header("etag").is_some();
+    let has_last_modified = response.header("last-modified").is_some();
+    if !has_etag && !has_last_modified && response.is_exposed("etag") {
+        errors.push(Error::Custom(
+            Rule::HttpNoCacheValidators,
+            "The response has neither an <code>ETag</code> nor a <code>Last-Modified</code> header, so apps cannot ask for the feed only if it has changed and download all of it every time.".to_string(),
+        ));
+    }
+
+    errors
+}
+
+/// Formats a number of bytes, e.g. “1.2 MB”.
+pub fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=999 => format!("{bytes} B"),
+        1_000..=999_999 => format!("{:.1} kB", bytes as f64 / 1e3),
+        _ => format!("{:.1} MB", bytes as f64 / 1e6),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)], body_size: usize) -> Response {
+        Response {
+            status: 200,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body_size,
+            elapsed_ms: None,
+            via_proxy: false,
+        }
+    }
+
+    fn rules(response: &Response) -> Vec<Rule> {
+        analyze(response)
+            .into_iter()
+            .map(|error| match error {
+                Error::Custom(rule, _) => rule,
+                _ => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze() {
+        // Hidden headers are not reported as missing.
+        let hidden = response(
+            &[
+                ("content-type", "application/rss+xml; charset=\"UTF-8\""),
+                ("content-length", "2000"),
+            ],
+            8000,
+        );
+        assert_eq!(hidden.charset(), Some("UTF-8"));
+        assert_eq!(hidden.is_compressed(), Some(true));
+        assert!(rules(&hidden).is_empty());
+
+        let exposed = response(
+            &[
+                ("content-type", "text/html"),
+                ("access-control-expose-headers", "*"),
+            ],
+            8000,
+        );
+        assert_eq!(
+            rules(&exposed),
+            [
+                Rule::HttpContentType,
+                Rule::HttpUncompressed,
+                Rule::HttpNoCacheValidators
+            ]
+        );
+
+        let html = response(&[("content-type", "text/<b>html</b>")], 100);
+        match &analyze(&html)[0] {
+            Error::Custom(_, message) => assert!(message.contains("text/&lt;b&gt;html&lt;/b&gt;")),
+            error => panic!("unexpected finding {error:?}"),
+        }
+
+        assert_eq!(format_size(1_234_567), "1.2 MB");
+    }
+}
@@ -0,0 +1,377 @@
+//! Semantic checks of [value for value](https://github.com/Podcastindex-org/podcast-namespace/blob/main/value/value.md)
+//! blocks that go beyond parsing.
+
+use super::{Error, Node, Rule, Value};
+
+/// Checks that the payment can be split between the recipients of `v4v_value` and records each
+/// recipient's effective percentage in the corresponding child of `node`.
+pub(super) fn analyze_splits(v4v_value: &badpod::podcast::Value, node: &mut Node) {
+    let recipients: Vec<(i64, bool)> = v4v_value
+        .value_recipient
+        .iter()
+        .map(|recipient| {
+            let split = match &recipient.split {
+                Some(badpod::Integer::Ok(i)) => (*i).max(0),
+                _ => 0,
+            };
+            let fee = matches!(&recipient.fee, Some(badpod::Bool::Ok(true)));
+            (split, fee)
+        })
+        .collect();
+    if recipients.is_empty() {
+        return;
+    }
+
+    let fee_total: i64 = recipients
+        .iter()
+        .filter(|(_, fee)| *fee)
+        .map(|(s, _)| s)
+        .sum();
+    let shares_total: i64 = recipients
+        .iter()
+        .filter(|(_, fee)| !*fee)
+        .map(|(s, _)| s)
+        .sum();
+
+    if fee_total > 100 {
+        node.errors.push(Error::Custom(
+            Rule::ValueFeesExceedPayment,
+            format!("Fee recipients take {fee_total}% of each payment, which is more than 100%."),
+        ));
+    } else if fee_total == 100 && shares_total > 0 {
+        node.errors.push(Error::Custom(
+            Rule::ValueFeesTakeAll,
+            "Fee recipients take 100% of each payment, so nothing is left for the other recipients.".to_string(),
+        ));
+    }
+    if shares_total == 0 && fee_total < 100 {
+        node.errors.push(Error::Custom(
+            Rule::ValueNoShares,
+            "None of the non-fee recipients has a positive split, so the payment cannot be distributed.".to_string(),
+        ));
+    } else if shares_total != 100 && shares_total > 0 {
+        node.errors.push(Error::Custom(
+            Rule::ValueSharesNotHundred,
+            format!("Splits of the non-fee recipients add up to {shares_total}. Splits are shares rather than percentages, so each recipient gets its split divided by {shares_total} of what remains after fees."),
+        ));
+    }
+
+    let percentages = effective_percentages(&recipients);
+    for (child, percentage) in node.children.iter_mut().zip(percentages) {
+        child.attributes.push((
+            "effective split".to_string(),
+            Value::Object(format!("{}%", format_percentage(percentage))),
+        ));
+    }
+
+    let keys: Vec<_> = v4v_value
+        .value_recipient
+        .iter()
+        .map(|r| {
+            (
+                r.type_.as_ref().map(|t| t.to_string()),
+                r.address.as_deref().map(str::trim),
+                r.custom_key.as_deref(),
+                r.custom_value.as_deref(),
+            )
+        })
+        .collect();
+    for (i, child) in node.children.iter_mut().enumerate() {
+        if keys[i].1.is_none() {
+            continue;
+        }
+        let duplicates: Vec<String> = keys
+            .iter()
+            .enumerate()
+            .filter(|(j, key)| *j != i && **key == keys[i])
+            .map(|(j, _)| (j + 1).to_string())
+            .collect();
+        if !duplicates.is_empty() {
+            child.errors.push(Error::Custom(
+                Rule::ValueDuplicateRecipient,
+                format!(
+                    "Same recipient as number {} in this block. Combine them into a single recipient.",
+                    duplicates.join(", ")
+                ),
+            ));
+        }
+    }
+}
+
+/// Percentage of a payment that each `(split, fee)` recipient receives.
+///
+/// Fee recipients take their split as a percentage off the top, and the remainder is divided
+/// between the other recipients in proportion to their splits.
+fn effective_percentages(recipients: &[(i64, bool)]) -> Vec<f64> {
+    let fee_total: i64 = recipients
+        .iter()
+        .filter(|(_, fee)| *fee)
+        .map(|(s, _)| s)
+        .sum();
+    let shares_total: i64 = recipients
+        .iter()
+        .filter(|(_, fee)| !*fee)
+        .map(|(s, _)| s)
+        .sum();
+    let remainder = (100 - fee_total).max(0) as f64;
+
+    recipients
+        .iter()
+        .map(|&(split, fee)| {
+            if fee {
+                split as f64
+            } else if shares_total > 0 {
+                remainder * split as f64 / shares_total as f64
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+fn format_percentage(percentage: f64) -> String {
+    let s = format!("{:.2}", percentage);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Checks the address against the format required by the recipient type.
+pub(super) fn analyze_address(type_: &str, address: &str) -> Option<Error> {
+    let reason = match type_ {
+        "node" if address.len() != 66 => {
+            format!(
+                "Node public keys must be 66 hexadecimal characters long, but this one has {}.",
+                address.chars().count()
+            )
+        }
+        "node" if !address.chars().all(|c| c.is_ascii_hexdigit()) => {
+            "Node public keys must only contain hexadecimal characters.".to_string()
+        }
+        "node" if !address.starts_with("02") && !address.starts_with("03") => {
+            "Node public keys are compressed and must start with <code>02</code> or <code>03</code>.".to_string()
+        }
+        "lnaddress" if !is_lightning_address(address) => {
+            "Lightning addresses must look like <code>name@example.com</code>.".to_string()
+        }
+        _ => return None,
+    };
+    Some(Error::InvalidAttributeWithReason(
+        "address".to_string(),
+        address.to_string(),
+        reason,
+    ))
+}
+
+fn is_lightning_address(address: &str) -> bool {
+    match address.split_once('@') {
+        Some((name, domain)) => {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.+".contains(c))
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && domain
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+        }
+        None => false,
+    }
+}
+
+/// Checks the shape of a keysend custom record.
+pub(super) fn analyze_custom_record(custom_key: &str, custom_value: &str) -> Vec<Error> {
+    let mut errors = Vec::new();
+
+    match custom_key.parse::<u64>() {
+        Ok(key) if key < 65536 => errors.push(Error::InvalidAttributeWithReason(
+            "customKey".to_string(),
+            custom_key.to_string(),
+            "Custom records must use TLV types of at least 65536, as lower ones are reserved by the Lightning protocol.".to_string(),
+        )),
+        Ok(_) => {}
+        Err(_) => errors.push(Error::InvalidAttributeWithReason(
+            "customKey".to_string(),
+            custom_key.to_string(),
+            "Custom keys must be TLV record types, i.e., non-negative integers.".to_string(),
+        )),
+    }
+
+    if custom_value.trim().is_empty() {
+        errors.push(Error::InvalidAttributeWithReason(
+            "customValue".to_string(),
+            custom_value.to_string(),
+            "Custom values must not be empty.".to_string(),
+        ));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_percentages() {
+        assert_eq!(
+            effective_percentages(&[(50, false), (40, false), (10, false)]),
+            vec![50.0, 40.0, 10.0]
+        );
+        assert_eq!(
+            effective_percentages(&[(1, false), (3, false), (20, true)]),
+            vec![20.0, 60.0, 20.0]
+        );
+        assert_eq!(format_percentage(100.0 / 3.0), "33.33");
+        assert_eq!(format_percentage(20.0), "20");
+        assert_eq!(format_percentage(12.5), "12.5");
+
+        assert!(is_lightning_address("satoshi@getalby.com"));
+        assert!(!is_lightning_address("satoshi@localhost"));
+        assert!(!is_lightning_address("getalby.com"));
+    }
+
+    /// `<podcast:value>` node of a channel with the given recipients.
+    fn value_node(recipients: &str) -> Node {
+        let report = super::super::validate_str(&format!(
+            r#"<rss version="2.0" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+                <channel>
+                    <title>Show</title>
+                    <podcast:value type="lightning" method="keysend">{recipients}</podcast:value>
+                </channel>
+            </rss>"#
+        ))
+        .unwrap();
+        report.root.children[0]
+            .children
+            .iter()
+            .find(|n| n.name.to_string() == "podcast:value")
+            .unwrap()
+            .clone()
+    }
+
+    fn recipient(split: i64, fee: bool, address: &str) -> String {
+        format!(
+            r#"<podcast:valueRecipient type="node" address="{address}" split="{split}" fee="{fee}"/>"#
+        )
+    }
+
+    fn rules(errors: &[Error]) -> Vec<Rule> {
+        errors
+            .iter()
+            .map(|error| match error {
+                Error::Custom(rule, _) => *rule,
+                _ => panic!("unexpected finding {error:?}"),
+            })
+            .collect()
+    }
+
+    fn effective_split(node: &Node) -> &str {
+        match node
+            .attributes
+            .iter()
+            .find(|(name, _)| name == "effective split")
+        {
+            Some((_, Value::Object(split))) => split,
+            _ => panic!("no effective split"),
+        }
+    }
+
+    const PUBKEY_1: &str = "02d5c1bf8b940dc9cadca86d1b0a3c37fbe39cee4c7e839e33bef9174531d27f52";
+    const PUBKEY_2: &str = "03ae9f91a0cb8ff43840e3c322c4c61f019d8c1c3cea15a25cfc425ac605e61a4a";
+    const PUBKEY_3: &str = "032f4ffbbafffbe51726ad3c164a3d0d37ec27bc67b29a159b0f49ae8ac21b8508";
+
+    #[test]
+    fn test_analyze_splits() {
+        let node = value_node(
+            &[
+                recipient(1, false, PUBKEY_1),
+                recipient(3, false, PUBKEY_2),
+                recipient(20, true, PUBKEY_3),
+            ]
+            .concat(),
+        );
+        assert_eq!(rules(&node.errors), [Rule::ValueSharesNotHundred]);
+        assert_eq!(
+            node.children
+                .iter()
+                .map(effective_split)
+                .collect::<Vec<_>>(),
+            ["20%", "60%", "20%"]
+        );
+
+        let node = value_node(
+            &[
+                recipient(90, false, PUBKEY_1),
+                recipient(10, false, PUBKEY_2),
+            ]
+            .concat(),
+        );
+        assert!(node.errors.is_empty());
+        assert!(node.children.iter().all(|child| child.errors.is_empty()));
+
+        let node =
+            value_node(&[recipient(60, true, PUBKEY_1), recipient(50, true, PUBKEY_2)].concat());
+        assert_eq!(rules(&node.errors), [Rule::ValueFeesExceedPayment]);
+
+        let node = value_node(
+            &[
+                recipient(100, false, PUBKEY_1),
+                recipient(100, true, PUBKEY_2),
+            ]
+            .concat(),
+        );
+        assert_eq!(rules(&node.errors), [Rule::ValueFeesTakeAll]);
+
+        let node =
+            value_node(&[recipient(0, false, PUBKEY_1), recipient(10, true, PUBKEY_2)].concat());
+        assert_eq!(rules(&node.errors), [Rule::ValueNoShares]);
+
+        let node = value_node(
+            &[
+                recipient(50, false, PUBKEY_1),
+                recipient(50, false, PUBKEY_1),
+            ]
+            .concat(),
+        );
+        assert!(node.errors.is_empty());
+        for child in &node.children {
+            assert_eq!(rules(&child.errors), [Rule::ValueDuplicateRecipient]);
+        }
+    }
+
+    #[test]
+    fn test_analyze_address() {
+        assert_eq!(analyze_address("node", PUBKEY_1), None);
+        assert_eq!(analyze_address("lnaddress", "satoshi@getalby.com"), None);
+
+        let reason = |type_: &str, address: &str| match analyze_address(type_, address) {
+            Some(Error::InvalidAttributeWithReason(attr, _, reason)) if attr == "address" => reason,
+            error => panic!("unexpected finding {error:?}"),
+        };
+        assert!(reason("node", &PUBKEY_1[..64]).contains("but this one has 64"));
+        assert!(reason("node", &PUBKEY_1.replace('f', "g")).contains("hexadecimal characters"));
+        assert!(reason("node", &PUBKEY_1.replacen("02", "04", 1)).contains("compressed"));
+        assert!(reason("lnaddress", "satoshi").contains("name@example.com"));
+    }
+
+    #[test]
+    fn test_analyze_custom_record() {
+        assert!(analyze_custom_record("696969", "eChoVKtO1KujpAA5HCoB").is_empty());
+        // Whitespace is not forbidden by the specification.
+        assert!(analyze_custom_record("7629169", "podcast guid").is_empty());
+
+        let attributes = |key: &str, value: &str| -> Vec<String> {
+            analyze_custom_record(key, value)
+                .into_iter()
+                .map(|error| match error {
+                    Error::InvalidAttributeWithReason(attr, _, _) => attr,
+                    _ => panic!("unexpected finding {error:?}"),
+                })
+                .collect()
+        };
+        assert_eq!(attributes("34349334", " "), ["customValue"]);
+        assert_eq!(attributes("1000", "x"), ["customKey"]);
+        assert_eq!(attributes("-1", ""), ["customKey", "customValue"]);
+    }
+}
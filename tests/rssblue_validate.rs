@@ -0,0 +1,112 @@
+//! Exit statuses and inputs of the `rssblue-validate` binary.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output, Stdio};
+
+const FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:itunes="http://www.itunes.com/dtds/podcast-1.0.dtd" xmlns:podcast="https://podcastindex.org/namespace/1.0">
+<channel>
+<title>Show</title>
+<description>A show.</description>
+<link>https://example.com/</link>
+<language>en</language>
+<podcast:locked>no</podcast:locked>
+<itunes:image href="https://example.com/cover.jpg"/>
+<itunes:category text="Technology"/>
+<itunes:explicit>false</itunes:explicit>
+<item>
+<title>Episode 1</title>
+<guid isPermaLink="false">episode-1</guid>
+<pubDate>Tue, 10 Jan 2023 08:00:00 GMT</pubDate>
+<enclosure url="https://example.com/1.mp3" length="1000" type="audio/mpeg"/>
+</item>
+</channel>
+</rss>
+"#;
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rssblue-validate"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_file() {
+    let dir = std::env::temp_dir().join(format!("rssblue-validate-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let valid = dir.join("valid.xml");
+    std::fs::write(&valid, FEED).unwrap();
+    let invalid = dir.join("invalid.xml");
+    std::fs::write(&invalid, FEED.replace("<title>Show</title>", "")).unwrap();
+
+    let output = run(&[valid.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(0), "{}", stdout(&output));
+
+    let output = run(&[invalid.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("Missing child <title>"));
+
+    let output = run(&[dir.join("missing.xml").to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(2));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_stdin() {
+    assert_eq!(run(&[], FEED).status.code(), Some(0));
+    assert_eq!(run(&["-"], FEED).status.code(), Some(0));
+    assert_eq!(run(&["-"], "not a feed").status.code(), Some(2));
+    assert_eq!(run(&["a.xml", "b.xml"], "").status.code(), Some(2));
+}
+
+#[test]
+fn test_url() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        for stream in listener.incoming().take(2) {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&stream);
+            reader.read_line(&mut request_line).unwrap();
+            // Skip the headers.
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let response = if request_line.starts_with("GET /old ") {
+                "HTTP/1.1 301 Moved Permanently\r\nLocation: /feed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+            } else {
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{FEED}",
+                    FEED.len()
+                )
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    let output = run(&[&format!("{base}/old")], "");
+    server.join().unwrap();
+    let stdout = stdout(&output);
+    assert_eq!(output.status.code(), Some(0), "{stdout}");
+    assert!(stdout.contains(&format!("301 {base}/old")));
+    assert!(stdout.contains(&format!("200 {base}/feed")));
+}